@@ -0,0 +1,196 @@
+use super::AuthToken;
+use crate::errors::Result;
+use crate::errors::{Neo4jClientErrorKind, Neo4jError, Neo4jErrorKind, Neo4jSecurityErrorKind};
+use futures::future::BoxFuture;
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use tokio::sync::Mutex;
+
+/// Provides the [`AuthToken`] that is used to authenticate connections.
+///
+/// The connection pool asks the manager for a token whenever it creates a connection
+/// and before it hands out a pooled connection again.
+/// Pooled connections that were authenticated with a different token are re-authenticated
+/// if the server supports Bolt 5.1 or later, and discarded otherwise.
+///
+/// An [`AuthToken`] is itself a manager that always returns the same token.
+/// Use [`RefreshingAuthTokenManager`] for credentials that expire, e.g. bearer tokens.
+pub trait AuthTokenManager: Debug + Send + Sync {
+    /// Returns the token to authenticate connections with.
+    fn get_token(&self) -> BoxFuture<'_, Result<AuthToken>>;
+
+    /// Called when the server rejected `token` with a security error.
+    ///
+    /// Returns `true` if the error has been handled, e.g. by invalidating the token,
+    /// in which case the failed query is retried on a newly authenticated connection.
+    fn handle_security_error<'a>(
+        &'a self,
+        token: &'a AuthToken,
+        error: &'a Neo4jError,
+    ) -> BoxFuture<'a, bool>;
+}
+
+impl AuthTokenManager for AuthToken {
+    fn get_token(&self) -> BoxFuture<'_, Result<AuthToken>> {
+        let token = self.clone();
+        Box::pin(async move { Ok(token) })
+    }
+
+    fn handle_security_error<'a>(
+        &'a self,
+        _token: &'a AuthToken,
+        error: &'a Neo4jError,
+    ) -> BoxFuture<'a, bool> {
+        // the same credentials are valid again on a new connection
+        let handled =
+            security_error_kind(error) == Some(Neo4jSecurityErrorKind::AuthorizationExpired);
+        Box::pin(async move { handled })
+    }
+}
+
+type TokenProvider = dyn Fn() -> BoxFuture<'static, Result<AuthToken>> + Send + Sync;
+
+/// An [`AuthTokenManager`] that fetches a new token from a provider
+/// once the server reports the current token as expired.
+///
+/// # Examples
+///
+/// ```no_run
+/// use neo4rs::{AuthToken, ConfigBuilder, RefreshingAuthTokenManager};
+///
+/// async fn fetch_sso_token() -> neo4rs::Result<String> {
+///     // ask the identity provider for a new token
+///     # Ok(String::new())
+/// }
+///
+/// let manager = RefreshingAuthTokenManager::new(|| async {
+///     fetch_sso_token().await.map(AuthToken::bearer)
+/// });
+/// let config = ConfigBuilder::default()
+///     .uri("neo4j://localhost:7687")
+///     .auth_manager(manager)
+///     .build()
+///     .unwrap();
+/// ```
+pub struct RefreshingAuthTokenManager {
+    provider: Box<TokenProvider>,
+    current: Mutex<Option<AuthToken>>,
+}
+
+impl RefreshingAuthTokenManager {
+    /// Creates a manager that calls `provider` for the first token and after every expiration.
+    pub fn new<F, Fut>(provider: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<AuthToken>> + Send + 'static,
+    {
+        Self {
+            provider: Box::new(move || Box::pin(provider())),
+            current: Mutex::new(None),
+        }
+    }
+}
+
+impl AuthTokenManager for RefreshingAuthTokenManager {
+    fn get_token(&self) -> BoxFuture<'_, Result<AuthToken>> {
+        Box::pin(async move {
+            // holding the lock while fetching lets concurrent callers wait for a single refresh
+            let mut current = self.current.lock().await;
+            if let Some(token) = current.as_ref() {
+                return Ok(token.clone());
+            }
+            let token = (self.provider)().await?;
+            *current = Some(token.clone());
+            Ok(token)
+        })
+    }
+
+    fn handle_security_error<'a>(
+        &'a self,
+        token: &'a AuthToken,
+        error: &'a Neo4jError,
+    ) -> BoxFuture<'a, bool> {
+        Box::pin(async move {
+            match security_error_kind(error) {
+                Some(Neo4jSecurityErrorKind::TokenExpired) => {
+                    let mut current = self.current.lock().await;
+                    if current.as_ref() == Some(token) {
+                        *current = None;
+                    }
+                    true
+                }
+                Some(Neo4jSecurityErrorKind::AuthorizationExpired) => true,
+                _ => false,
+            }
+        })
+    }
+}
+
+impl Debug for RefreshingAuthTokenManager {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RefreshingAuthTokenManager")
+            .finish_non_exhaustive()
+    }
+}
+
+fn security_error_kind(error: &Neo4jError) -> Option<Neo4jSecurityErrorKind> {
+    match error.kind() {
+        Neo4jErrorKind::Client(Neo4jClientErrorKind::Security(kind)) => Some(kind),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    fn error(code: &str) -> Neo4jError {
+        Neo4jError::new(code.to_owned(), "oh no".to_owned())
+    }
+
+    #[tokio::test]
+    async fn static_token() {
+        let token = AuthToken::basic("user", "pass");
+        assert_eq!(token.get_token().await.unwrap(), token);
+
+        let expired = error("Neo.ClientError.Security.TokenExpired");
+        assert!(!token.handle_security_error(&token, &expired).await);
+
+        let expired = error("Neo.ClientError.Security.AuthorizationExpired");
+        assert!(token.handle_security_error(&token, &expired).await);
+    }
+
+    #[tokio::test]
+    async fn refreshes_expired_token() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let manager = RefreshingAuthTokenManager::new({
+            let calls = Arc::clone(&calls);
+            move || {
+                let call = calls.fetch_add(1, Ordering::SeqCst);
+                async move { Ok(AuthToken::bearer(format!("token-{call}"))) }
+            }
+        });
+
+        let token = manager.get_token().await.unwrap();
+        assert_eq!(token, AuthToken::bearer("token-0"));
+        assert_eq!(manager.get_token().await.unwrap(), token);
+
+        let unauthorized = error("Neo.ClientError.Security.Unauthorized");
+        assert!(!manager.handle_security_error(&token, &unauthorized).await);
+        assert_eq!(manager.get_token().await.unwrap(), token);
+
+        let expired = error("Neo.ClientError.Security.TokenExpired");
+        assert!(manager.handle_security_error(&token, &expired).await);
+        let refreshed = manager.get_token().await.unwrap();
+        assert_eq!(refreshed, AuthToken::bearer("token-1"));
+
+        // a late failure of the old token does not discard the refreshed one
+        assert!(manager.handle_security_error(&token, &expired).await);
+        assert_eq!(manager.get_token().await.unwrap(), refreshed);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -1,3 +1,9 @@
+mod manager;
+mod token;
+
+pub use manager::{AuthTokenManager, RefreshingAuthTokenManager};
+pub use token::AuthToken;
+
 use std::path::{Path, PathBuf};
 
 #[derive(Debug, PartialEq, Clone)]
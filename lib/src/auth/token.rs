@@ -0,0 +1,216 @@
+use crate::types::{BoltMap, BoltType};
+use std::fmt::{Debug, Formatter};
+
+/// The credentials that are used to authenticate a connection.
+///
+/// The token is sent in the `HELLO` message or, since Bolt 5.1,
+/// in a separate `LOGON` message.
+///
+/// # Examples
+///
+/// ```
+/// use neo4rs::AuthToken;
+///
+/// let basic = AuthToken::basic("neo4j", "password");
+/// let bearer = AuthToken::bearer("an SSO issued token");
+/// let custom = AuthToken::custom("custom", "neo4j", "password", None, None);
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct AuthToken {
+    pub(crate) scheme: String,
+    pub(crate) principal: Option<String>,
+    pub(crate) credentials: Option<String>,
+    pub(crate) realm: Option<String>,
+    pub(crate) parameters: Option<BoltMap>,
+}
+
+impl AuthToken {
+    /// Authenticate with a username and a password.
+    ///
+    /// Use [`AuthToken::with_realm`] to authenticate against a specific realm.
+    pub fn basic(user: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            scheme: "basic".to_owned(),
+            principal: Some(user.into()),
+            credentials: Some(password.into()),
+            realm: None,
+            parameters: None,
+        }
+    }
+
+    /// Authenticate with a bearer token, e.g. one that was issued by an SSO provider.
+    pub fn bearer(token: impl Into<String>) -> Self {
+        Self {
+            scheme: "bearer".to_owned(),
+            principal: None,
+            credentials: Some(token.into()),
+            realm: None,
+            parameters: None,
+        }
+    }
+
+    /// Authenticate with a base64 encoded kerberos ticket.
+    pub fn kerberos(base64_encoded_ticket: impl Into<String>) -> Self {
+        Self {
+            scheme: "kerberos".to_owned(),
+            principal: Some(String::new()),
+            credentials: Some(base64_encoded_ticket.into()),
+            realm: None,
+            parameters: None,
+        }
+    }
+
+    /// Do not authenticate, for servers that have authentication disabled.
+    pub fn none() -> Self {
+        Self {
+            scheme: "none".to_owned(),
+            principal: None,
+            credentials: None,
+            realm: None,
+            parameters: None,
+        }
+    }
+
+    /// Authenticate with a scheme that is provided by a custom authentication plugin.
+    pub fn custom(
+        scheme: impl Into<String>,
+        principal: impl Into<String>,
+        credentials: impl Into<String>,
+        realm: Option<String>,
+        parameters: Option<BoltMap>,
+    ) -> Self {
+        Self {
+            scheme: scheme.into(),
+            principal: Some(principal.into()),
+            credentials: Some(credentials.into()),
+            realm,
+            parameters,
+        }
+    }
+
+    /// Authenticate against the given realm.
+    pub fn with_realm(self, realm: impl Into<String>) -> Self {
+        Self {
+            realm: Some(realm.into()),
+            ..self
+        }
+    }
+
+    /// The authentication scheme, e.g. `basic` or `bearer`.
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    /// The principal that is being authenticated, e.g. the username.
+    pub fn principal(&self) -> Option<&str> {
+        self.principal.as_deref()
+    }
+
+    /// The realm to authenticate against.
+    pub fn realm(&self) -> Option<&str> {
+        self.realm.as_deref()
+    }
+
+    /// Adds the authentication entries to the metadata of a `HELLO` or `LOGON` message.
+    #[cfg_attr(feature = "unstable-bolt-protocol-impl-v2", allow(dead_code))]
+    pub(crate) fn insert_into(&self, map: &mut BoltMap) {
+        map.put("scheme".into(), self.scheme.as_str().into());
+        if let Some(principal) = &self.principal {
+            map.put("principal".into(), principal.as_str().into());
+        }
+        if let Some(credentials) = &self.credentials {
+            map.put("credentials".into(), credentials.as_str().into());
+        }
+        if let Some(realm) = &self.realm {
+            map.put("realm".into(), realm.as_str().into());
+        }
+        if let Some(parameters) = &self.parameters {
+            map.put("parameters".into(), BoltType::Map(parameters.clone()));
+        }
+    }
+}
+
+impl Debug for AuthToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthToken")
+            .field("scheme", &self.scheme)
+            .field("principal", &self.principal)
+            .field("credentials", &self.credentials.as_ref().map(|_| "***"))
+            .field("realm", &self.realm)
+            .field("parameters", &self.parameters)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(token: &AuthToken) -> BoltMap {
+        let mut map = BoltMap::default();
+        token.insert_into(&mut map);
+        map
+    }
+
+    #[test]
+    fn basic() {
+        let map = entries(&AuthToken::basic("user", "pass"));
+        assert_eq!(map.value.len(), 3);
+        assert_eq!(map.get::<String>("scheme").unwrap(), "basic");
+        assert_eq!(map.get::<String>("principal").unwrap(), "user");
+        assert_eq!(map.get::<String>("credentials").unwrap(), "pass");
+
+        let map = entries(&AuthToken::basic("user", "pass").with_realm("native"));
+        assert_eq!(map.value.len(), 4);
+        assert_eq!(map.get::<String>("realm").unwrap(), "native");
+    }
+
+    #[test]
+    fn bearer() {
+        let map = entries(&AuthToken::bearer("token"));
+        assert_eq!(map.value.len(), 2);
+        assert_eq!(map.get::<String>("scheme").unwrap(), "bearer");
+        assert_eq!(map.get::<String>("credentials").unwrap(), "token");
+    }
+
+    #[test]
+    fn kerberos() {
+        let map = entries(&AuthToken::kerberos("dGlja2V0"));
+        assert_eq!(map.value.len(), 3);
+        assert_eq!(map.get::<String>("scheme").unwrap(), "kerberos");
+        assert_eq!(map.get::<String>("principal").unwrap(), "");
+        assert_eq!(map.get::<String>("credentials").unwrap(), "dGlja2V0");
+    }
+
+    #[test]
+    fn none() {
+        let map = entries(&AuthToken::none());
+        assert_eq!(map.value.len(), 1);
+        assert_eq!(map.get::<String>("scheme").unwrap(), "none");
+    }
+
+    #[test]
+    fn custom() {
+        let parameters = [("tenant".into(), "acme".into())].into_iter().collect();
+        let token = AuthToken::custom(
+            "plugin",
+            "user",
+            "pass",
+            Some("realm".to_owned()),
+            Some(parameters),
+        );
+        let map = entries(&token);
+        assert_eq!(map.value.len(), 5);
+        assert_eq!(map.get::<String>("scheme").unwrap(), "plugin");
+        assert_eq!(map.get::<String>("realm").unwrap(), "realm");
+        let parameters = map.get::<BoltMap>("parameters").unwrap();
+        assert_eq!(parameters.get::<String>("tenant").unwrap(), "acme");
+    }
+
+    #[test]
+    fn debug_hides_credentials() {
+        let debug = format!("{:?}", AuthToken::basic("user", "secret"));
+        assert!(debug.contains("user"));
+        assert!(!debug.contains("secret"));
+    }
+}
@@ -1,10 +1,10 @@
 use crate::bolt::{ExpectedResponse, Summary};
-use crate::{Database, Version};
+use crate::{BoltString, BoltType, Database, Version};
 use serde::ser::SerializeMap;
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Begin<'a> {
     metadata: BeginMeta<'a>,
 }
@@ -22,10 +22,10 @@ pub struct Extra<'a> {
     pub(crate) imp_user: Option<&'a str>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct TxMetadata(Vec<(String, String)>);
+#[derive(Debug, Clone, PartialEq)]
+pub struct TxMetadata(Vec<(BoltString, BoltType)>);
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct BeginMeta<'a> {
     pub(crate) bookmarks: Vec<String>,
     pub(crate) tx_timeout: Option<u32>,
@@ -71,7 +71,15 @@ impl<'a> BeginBuilder<'a> {
         self
     }
 
-    pub fn with_tx_metadata(mut self, tx_metadata: Vec<(String, String)>) -> Self {
+    pub fn with_tx_metadata<K, V>(mut self, tx_metadata: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<BoltString>,
+        V: Into<BoltType>,
+    {
+        let tx_metadata = tx_metadata
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
         self.tx_metadata = Some(TxMetadata(tx_metadata));
         self
     }
@@ -212,6 +220,7 @@ mod tests {
     use super::Begin;
     use crate::bolt::Message;
     use crate::packstream::bolt;
+    use crate::BoltType;
     use crate::{Database, Version};
 
     #[test]
@@ -271,4 +280,63 @@ mod tests {
 
         assert_eq!(bytes, expected);
     }
+
+    #[test]
+    fn serialize_v5() {
+        for version in [
+            Version::V5_0,
+            Version::V5_1,
+            Version::V5_2,
+            Version::V5_3,
+            Version::V5_4,
+        ] {
+            let begin = Begin::builder(Some("neo4j"))
+                .with_imp_user("my_user")
+                .build(version);
+            let bytes = begin.to_bytes().unwrap();
+
+            let expected = bolt()
+                .structure(1, 0x11)
+                .tiny_map(4)
+                .tiny_string("bookmarks")
+                .tiny_list(0)
+                .tiny_string("mode")
+                .tiny_string("w")
+                .tiny_string("db")
+                .tiny_string("neo4j")
+                .tiny_string("imp_user")
+                .tiny_string("my_user")
+                .build();
+
+            assert_eq!(bytes, expected, "version {version}");
+        }
+    }
+
+    #[test]
+    fn serialize_tx_config() {
+        let begin = Begin::builder(None)
+            .with_tx_timeout(1500)
+            .with_tx_metadata([("app", BoltType::from("reporting")), ("batch", 7.into())])
+            .build(Version::V5_0);
+        let bytes = begin.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(1, 0x11)
+            .tiny_map(4)
+            .tiny_string("bookmarks")
+            .tiny_list(0)
+            .tiny_string("mode")
+            .tiny_string("w")
+            .tiny_string("tx_timeout")
+            .int16(1500)
+            .tiny_string("tx_metadata")
+            .tiny_map(2)
+            .tiny_string("app")
+            .tiny_string("reporting")
+            .tiny_string("batch")
+            .tiny_int(7)
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
 }
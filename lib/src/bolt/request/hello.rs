@@ -1,30 +1,35 @@
-use std::borrow::Borrow;
+use std::{borrow::Borrow, time::Duration};
 
+use super::logon::Auth;
 use crate::{
     bolt::{ExpectedResponse, Summary},
-    Version,
+    AuthToken, BoltMap, Version,
 };
 use serde::{ser::SerializeMap, Deserialize, Deserializer, Serialize};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Hello<'a> {
     metadata: Meta<'a>,
 }
 
 pub struct HelloBuilder<'a> {
-    scheme: &'a str,
-    principal: &'a str,
-    credentials: &'a str,
+    auth: Auth<'a>,
     user_agent: &'a str,
     routing: ServerRouting<'a>,
 }
 
 impl<'a> HelloBuilder<'a> {
     pub fn new(principal: &'a str, credentials: &'a str) -> Self {
+        Self::init(Auth::basic(principal, credentials))
+    }
+
+    pub fn from_auth(auth: &'a AuthToken) -> Self {
+        Self::init(Auth::from(auth))
+    }
+
+    fn init(auth: Auth<'a>) -> Self {
         Self {
-            scheme: "basic",
-            principal,
-            credentials,
+            auth,
             user_agent: "neo4rs",
             routing: ServerRouting::No,
         }
@@ -45,9 +50,7 @@ impl<'a> HelloBuilder<'a> {
 
     pub fn build(self, version: Version) -> Hello<'a> {
         let Self {
-            scheme,
-            principal,
-            credentials,
+            auth,
             user_agent,
             mut routing,
         } = self;
@@ -56,11 +59,18 @@ impl<'a> HelloBuilder<'a> {
             routing = ServerRouting::No;
         }
 
+        // Since 5.1, authentication is sent in a separate LOGON message
+        let auth = (version < Version::V5_1).then_some(auth);
+        let bolt_agent = (version >= Version::V5_3).then_some(BoltAgent::NEO4RS);
+
         let metadata = Meta {
+            scheme: auth.as_ref().map(|a| a.scheme),
+            principal: auth.as_ref().and_then(|a| a.principal),
+            credentials: auth.as_ref().and_then(|a| a.credentials),
+            realm: auth.as_ref().and_then(|a| a.realm),
+            parameters: auth.as_ref().and_then(|a| a.parameters),
             user_agent,
-            scheme,
-            principal,
-            credentials,
+            bolt_agent,
             routing,
         };
         Hello { metadata }
@@ -73,16 +83,38 @@ impl<'a> Hello<'a> {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 struct Meta<'a> {
-    scheme: &'a str,
-    principal: &'a str,
-    credentials: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scheme: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    principal: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    credentials: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    realm: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parameters: Option<&'a BoltMap>,
     user_agent: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bolt_agent: Option<BoltAgent>,
     #[serde(skip_serializing_if = "ServerRouting::is_none")]
     routing: ServerRouting<'a>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+struct BoltAgent {
+    product: &'static str,
+    language: &'static str,
+}
+
+impl BoltAgent {
+    const NEO4RS: Self = Self {
+        product: concat!("neo4rs/", env!("CARGO_PKG_VERSION")),
+        language: "Rust",
+    };
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum ServerRouting<'a> {
     No,
@@ -128,6 +160,15 @@ pub struct ConnectionsHints {
     connection_recv_timeout_seconds: Option<u32>,
 }
 
+impl ConnectionsHints {
+    /// How long the server may stay silent before the connection is considered broken.
+    pub(crate) fn recv_timeout(&self) -> Option<Duration> {
+        self.connection_recv_timeout_seconds
+            .filter(|&seconds| seconds > 0)
+            .map(|seconds| Duration::from_secs(seconds.into()))
+    }
+}
+
 impl ExpectedResponse for Hello<'_> {
     type Response = Summary<Response>;
 }
@@ -146,7 +187,7 @@ mod tests {
     use super::*;
     use crate::{
         bolt::{Message as _, MessageResponse as _},
-        packstream::bolt,
+        packstream::{bolt, to_bytes},
     };
 
     #[test]
@@ -247,6 +288,97 @@ mod tests {
         assert_eq!(bytes, expected);
     }
 
+    #[test]
+    fn serialize_v5_0_with_auth() {
+        let hello = Hello::builder("user", "pass")
+            .with_routing([])
+            .build(Version::V5_0);
+        let bytes = hello.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(1, 0x01)
+            .tiny_map(5)
+            .tiny_string("scheme")
+            .tiny_string("basic")
+            .tiny_string("principal")
+            .tiny_string("user")
+            .tiny_string("credentials")
+            .tiny_string("pass")
+            .tiny_string("user_agent")
+            .tiny_string("neo4rs")
+            .tiny_string("routing")
+            .tiny_map(0)
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn serialize_v5_0_with_auth_token() {
+        let token = AuthToken::kerberos("dGlja2V0");
+        let hello = HelloBuilder::from_auth(&token).build(Version::V5_0);
+        let bytes = hello.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(1, 0x01)
+            .tiny_map(4)
+            .tiny_string("scheme")
+            .tiny_string("kerberos")
+            .tiny_string("principal")
+            .tiny_string("")
+            .tiny_string("credentials")
+            .tiny_string("dGlja2V0")
+            .tiny_string("user_agent")
+            .tiny_string("neo4rs")
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn serialize_v5_1_without_auth() {
+        for version in [Version::V5_1, Version::V5_2] {
+            let hello = Hello::builder("user", "pass")
+                .with_routing([])
+                .build(version);
+            let bytes = hello.to_bytes().unwrap();
+
+            let expected = bolt()
+                .structure(1, 0x01)
+                .tiny_map(2)
+                .tiny_string("user_agent")
+                .tiny_string("neo4rs")
+                .tiny_string("routing")
+                .tiny_map(0)
+                .build();
+
+            assert_eq!(bytes, expected, "version {version}");
+        }
+    }
+
+    #[test]
+    fn serialize_v5_3_with_bolt_agent() {
+        for version in [Version::V5_3, Version::V5_4] {
+            let hello = Hello::builder("user", "pass").build(version);
+            let bytes = hello.to_bytes().unwrap();
+
+            let expected = bolt()
+                .structure(1, 0x01)
+                .tiny_map(2)
+                .tiny_string("user_agent")
+                .tiny_string("neo4rs")
+                .tiny_string("bolt_agent")
+                .tiny_map(2)
+                .tiny_string("product")
+                .extend(to_bytes(&BoltAgent::NEO4RS.product).unwrap())
+                .tiny_string("language")
+                .tiny_string("Rust")
+                .build();
+
+            assert_eq!(bytes, expected, "version {version}");
+        }
+    }
+
     #[test]
     fn parse() {
         let data = bolt()
@@ -283,12 +415,8 @@ mod tests {
         assert_eq!(response.connection_id, "bolt-31");
         assert!(response.hints.is_some());
         assert_eq!(
-            response
-                .hints
-                .unwrap()
-                .connection_recv_timeout_seconds
-                .unwrap(),
-            120
+            response.hints.unwrap().recv_timeout(),
+            Some(Duration::from_secs(120))
         );
     }
 }
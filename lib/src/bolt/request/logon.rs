@@ -0,0 +1,145 @@
+use crate::{
+    bolt::{ExpectedResponse, Summary},
+    AuthToken, BoltMap,
+};
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Logon<'a> {
+    auth: Auth<'a>,
+}
+
+impl<'a> Logon<'a> {
+    pub fn new(auth: &'a AuthToken) -> Self {
+        Self {
+            auth: Auth::from(auth),
+        }
+    }
+
+    pub fn basic(principal: &'a str, credentials: &'a str) -> Self {
+        Self {
+            auth: Auth::basic(principal, credentials),
+        }
+    }
+}
+
+/// The authentication entries of a `LOGON` or `HELLO` message.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(super) struct Auth<'a> {
+    pub(super) scheme: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) principal: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) credentials: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) realm: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) parameters: Option<&'a BoltMap>,
+}
+
+impl<'a> Auth<'a> {
+    pub(super) fn basic(principal: &'a str, credentials: &'a str) -> Self {
+        Self {
+            scheme: "basic",
+            principal: Some(principal),
+            credentials: Some(credentials),
+            realm: None,
+            parameters: None,
+        }
+    }
+}
+
+impl<'a> From<&'a AuthToken> for Auth<'a> {
+    fn from(auth: &'a AuthToken) -> Self {
+        Self {
+            scheme: &auth.scheme,
+            principal: auth.principal.as_deref(),
+            credentials: auth.credentials.as_deref(),
+            realm: auth.realm.as_deref(),
+            parameters: auth.parameters.as_ref(),
+        }
+    }
+}
+
+impl ExpectedResponse for Logon<'_> {
+    type Response = Summary<()>;
+}
+
+impl Serialize for Logon<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_newtype_variant("Request", 0x6A, "LOGON", &self.auth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bolt::Message as _, packstream::bolt};
+
+    #[test]
+    fn serialize() {
+        let logon = Logon::basic("user", "pass");
+        let bytes = logon.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(1, 0x6A)
+            .tiny_map(3)
+            .tiny_string("scheme")
+            .tiny_string("basic")
+            .tiny_string("principal")
+            .tiny_string("user")
+            .tiny_string("credentials")
+            .tiny_string("pass")
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn serialize_auth_token() {
+        let token = AuthToken::bearer("token").with_realm("sso");
+        let logon = Logon::new(&token);
+        let bytes = logon.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(1, 0x6A)
+            .tiny_map(3)
+            .tiny_string("scheme")
+            .tiny_string("bearer")
+            .tiny_string("credentials")
+            .tiny_string("token")
+            .tiny_string("realm")
+            .tiny_string("sso")
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn serialize_custom_auth_token() {
+        let parameters = [("tenant".into(), "acme".into())].into_iter().collect();
+        let token = AuthToken::custom("plugin", "user", "pass", None, Some(parameters));
+        let logon = Logon::new(&token);
+        let bytes = logon.to_bytes().unwrap();
+
+        let expected = bolt()
+            .structure(1, 0x6A)
+            .tiny_map(4)
+            .tiny_string("scheme")
+            .tiny_string("plugin")
+            .tiny_string("principal")
+            .tiny_string("user")
+            .tiny_string("credentials")
+            .tiny_string("pass")
+            .tiny_string("parameters")
+            .tiny_map(1)
+            .tiny_string("tenant")
+            .tiny_string("acme")
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+}
@@ -4,6 +4,8 @@ mod discard;
 mod extra;
 mod goodbye;
 mod hello;
+mod logoff;
+mod logon;
 mod pull;
 mod reset;
 mod rollback;
@@ -15,6 +17,8 @@ pub use discard::Discard;
 pub use extra::WrapExtra;
 pub use goodbye::Goodbye;
 pub use hello::{ConnectionsHints, Hello, HelloBuilder};
+pub use logoff::Logoff;
+pub use logon::Logon;
 pub use pull::Pull;
 pub use reset::Reset;
 pub use rollback::Rollback;
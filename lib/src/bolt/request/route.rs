@@ -199,4 +199,32 @@ mod tests {
         assert_eq!(response.rt.db.unwrap().as_ref(), "neo4j");
         assert_eq!(response.rt.servers.len(), 1);
     }
+
+    #[test]
+    fn serialize_with_db_v5() {
+        for version in [
+            Version::V5_0,
+            Version::V5_1,
+            Version::V5_2,
+            Version::V5_3,
+            Version::V5_4,
+        ] {
+            let builder = RouteBuilder::new(Routing::Yes([].into()), vec![]);
+            let route = builder.with_db("neo4j".into()).build(version);
+            let serialized = route.to_bytes().unwrap();
+
+            let expected = bolt()
+                .structure(3, 0x66)
+                .tiny_map(0)
+                .tiny_list(0)
+                .tiny_map(2)
+                .tiny_string("db")
+                .tiny_string("neo4j")
+                .tiny_string("imp_user")
+                .null()
+                .build();
+
+            assert_eq!(serialized, expected, "version {version}");
+        }
+    }
 }
@@ -295,12 +295,16 @@ impl From<Bolt> for crate::BoltType {
         fn conv_unrel(rel: urel::UnboundRelationship) -> BoltUnboundedRelation {
             let id = BoltInteger::new(rel.id().try_into().unwrap());
             let typ = BoltString::from(rel.typ());
+            let element_id = rel.element_id().map(BoltString::from);
             let properties = rel.into::<Bolt>().unwrap();
             let properties = BoltType::from(properties);
             let BoltType::Map(properties) = properties else {
                 panic!("properties should be a map");
             };
-            BoltUnboundedRelation::new(id, typ, properties)
+            BoltUnboundedRelation {
+                element_id,
+                ..BoltUnboundedRelation::new(id, typ, properties)
+            }
         }
 
         match value {
@@ -326,22 +330,29 @@ impl From<Bolt> for crate::BoltType {
                     .cloned()
                     .map(BoltType::from)
                     .collect::<Vec<_>>();
+                let element_id = v.element_id().map(BoltString::from);
                 let properties = v.into::<Bolt>().unwrap();
                 let properties = BoltType::from(properties);
                 let BoltType::Map(properties) = properties else {
                     panic!("properties should be a map");
                 };
-                Self::Node(BoltNode::new(
-                    BoltInteger::new(id.try_into().unwrap()),
-                    BoltList::from(labels),
-                    properties,
-                ))
+                Self::Node(BoltNode {
+                    element_id,
+                    ..BoltNode::new(
+                        BoltInteger::new(id.try_into().unwrap()),
+                        BoltList::from(labels),
+                        properties,
+                    )
+                })
             }
             Bolt::Relationship(v) => {
                 let id = v.id();
                 let start_node_id = v.start_node_id();
                 let end_node_id = v.end_node_id();
                 let typ = BoltString::from(v.typ());
+                let element_id = v.element_id().map(BoltString::from);
+                let start_node_element_id = v.start_node_element_id().map(BoltString::from);
+                let end_node_element_id = v.end_node_element_id().map(BoltString::from);
                 let properties = v.into::<Bolt>().unwrap();
                 let properties = BoltType::from(properties);
                 let BoltType::Map(properties) = properties else {
@@ -353,6 +364,9 @@ impl From<Bolt> for crate::BoltType {
                     end_node_id: BoltInteger::new(end_node_id.try_into().unwrap()),
                     typ,
                     properties,
+                    element_id,
+                    start_node_element_id,
+                    end_node_element_id,
                 })
             }
             Bolt::Path(v) => {
@@ -394,13 +408,19 @@ impl From<Bolt> for crate::BoltType {
             Bolt::LocalTime(v) => Self::LocalTime(BoltLocalTime {
                 nanoseconds: BoltInteger::new(v.nanoseconds_since_midnight().try_into().unwrap()),
             }),
+            // BoltDateTime and BoltDateTimeZoneId store local seconds, not UTC seconds
             Bolt::DateTime(v) => Self::DateTime(BoltDateTime {
-                seconds: v.seconds_since_epoch().into(),
+                seconds: (v.seconds_since_epoch() + i64::from(v.timezone_offset_seconds())).into(),
                 nanoseconds: BoltInteger::new(v.nanoseconds().into()),
                 tz_offset_seconds: v.timezone_offset_seconds().into(),
             }),
             Bolt::DateTimeZoneId(v) => Self::DateTimeZoneId(BoltDateTimeZoneId {
-                seconds: v.seconds_since_epoch().into(),
+                seconds: v
+                    .as_chrono_datetime()
+                    .map_or(v.seconds_since_epoch(), |dt| {
+                        dt.naive_local().and_utc().timestamp()
+                    })
+                    .into(),
                 nanoseconds: BoltInteger::new(v.nanoseconds().into()),
                 tz_id: v.timezone_identifier().into(),
             }),
@@ -438,3 +458,64 @@ impl From<Bolt> for crate::BoltType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        packstream::{bolt, from_bytes},
+        BoltDateTime, BoltDateTimeZoneId, BoltInteger, BoltString, BoltType,
+    };
+
+    #[test]
+    fn convert_utc_datetime_to_local_seconds() {
+        let data = bolt()
+            .structure(3, 0x49)
+            .int16(4500)
+            .tiny_int(42)
+            .int16(3600)
+            .build();
+        let value: Bolt = from_bytes(data).unwrap();
+
+        let BoltType::DateTime(BoltDateTime { seconds, .. }) = BoltType::from(value) else {
+            panic!("expected a datetime");
+        };
+        assert_eq!(seconds, BoltInteger::new(8100));
+    }
+
+    #[test]
+    fn convert_utc_datetime_zone_id_to_local_seconds() {
+        let data = bolt()
+            .structure(3, 0x69)
+            .int16(4500)
+            .tiny_int(42)
+            .string8("Europe/Stockholm")
+            .build();
+        let value: Bolt = from_bytes(data).unwrap();
+
+        let BoltType::DateTimeZoneId(BoltDateTimeZoneId { seconds, tz_id, .. }) =
+            BoltType::from(value)
+        else {
+            panic!("expected a datetime with zone id");
+        };
+        assert_eq!(seconds, BoltInteger::new(8100));
+        assert_eq!(tz_id, BoltString::from("Europe/Stockholm"));
+    }
+
+    #[test]
+    fn convert_node_with_element_id() {
+        let data = bolt()
+            .structure(4, 0x4E)
+            .tiny_int(42)
+            .tiny_list(0)
+            .tiny_map(0)
+            .tiny_string("4:42")
+            .build();
+        let value: Bolt = from_bytes(data).unwrap();
+
+        let BoltType::Node(node) = BoltType::from(value) else {
+            panic!("expected a node");
+        };
+        assert_eq!(node.element_id, Some(BoltString::from("4:42")));
+    }
+}
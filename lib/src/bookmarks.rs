@@ -1,4 +1,190 @@
+// only exchanged with the server by the bolt v2 implementation
+#![cfg_attr(not(feature = "unstable-bolt-protocol-impl-v2"), allow(dead_code))]
+
+use crate::errors::Result;
+use futures::future::BoxFuture;
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+use std::sync::Arc;
+use std::{collections::HashMap, fmt::Debug, sync::Mutex};
+
 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
 pub(crate) trait Bookmark {
     fn get_bookmark(&self) -> Option<&str>;
 }
+
+/// Keeps track of the bookmarks that make transactions causally consistent.
+///
+/// A transaction waits for the bookmarks of its database before it starts,
+/// so that it sees the writes of all transactions that produced them.
+/// Once it is committed, the bookmarks it waited for are replaced with the one it produced.
+/// The database is `None` for the configured or home database of the server.
+///
+/// The manager is shared by a [`crate::Graph`] and all of its clones.
+/// The default is an [`InMemoryBookmarkManager`], set a manager backed by a shared store
+/// with [`crate::ConfigBuilder::bookmark_manager`] to get causal consistency across processes.
+pub trait BookmarkManager: Debug + Send + Sync {
+    /// Returns the bookmarks that a transaction on `db` has to wait for.
+    fn get_bookmarks<'a>(&'a self, db: Option<&'a str>) -> BoxFuture<'a, Result<Vec<String>>>;
+
+    /// Called when a transaction on `db` that waited for `previous` has been committed
+    /// and produced the bookmark `new`.
+    fn update_bookmarks<'a>(
+        &'a self,
+        db: Option<&'a str>,
+        previous: &'a [String],
+        new: &'a str,
+    ) -> BoxFuture<'a, ()>;
+}
+
+/// A [`BookmarkManager`] that keeps the bookmarks of every database in memory.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+/// # futures::executor::block_on(async {
+/// use neo4rs::{BookmarkManager, InMemoryBookmarkManager};
+///
+/// let manager = InMemoryBookmarkManager::new();
+/// manager.update_bookmarks(Some("movies"), &[], "bm-1").await;
+/// assert_eq!(manager.get_bookmarks(Some("movies")).await?, ["bm-1"]);
+/// assert!(manager.get_bookmarks(None).await?.is_empty());
+/// # Ok::<_, neo4rs::Error>(())
+/// # }).unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct InMemoryBookmarkManager {
+    bookmarks: Mutex<HashMap<Option<String>, Vec<String>>>,
+}
+
+impl InMemoryBookmarkManager {
+    /// Creates a manager without any bookmarks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the initial bookmarks of `db`, e.g. the ones of another process.
+    pub fn with_bookmarks<B: Into<String>>(
+        self,
+        db: Option<&str>,
+        bookmarks: impl IntoIterator<Item = B>,
+    ) -> Self {
+        self.bookmarks.lock().unwrap().insert(
+            db.map(str::to_owned),
+            bookmarks.into_iter().map(Into::into).collect(),
+        );
+        self
+    }
+}
+
+impl BookmarkManager for InMemoryBookmarkManager {
+    fn get_bookmarks<'a>(&'a self, db: Option<&'a str>) -> BoxFuture<'a, Result<Vec<String>>> {
+        let bookmarks = self
+            .bookmarks
+            .lock()
+            .unwrap()
+            .get(&db.map(str::to_owned))
+            .cloned()
+            .unwrap_or_default();
+        Box::pin(async move { Ok(bookmarks) })
+    }
+
+    fn update_bookmarks<'a>(
+        &'a self,
+        db: Option<&'a str>,
+        previous: &'a [String],
+        new: &'a str,
+    ) -> BoxFuture<'a, ()> {
+        {
+            let mut bookmarks = self.bookmarks.lock().unwrap();
+            let bookmarks = bookmarks.entry(db.map(str::to_owned)).or_default();
+            bookmarks.retain(|bookmark| !previous.contains(bookmark));
+            if !bookmarks.iter().any(|bookmark| bookmark == new) {
+                bookmarks.push(new.to_owned());
+            }
+        }
+        Box::pin(async {})
+    }
+}
+
+/// The bookmarks of a session, shared with the transactions it started,
+/// so that every committed transaction advances the bookmarks of its session.
+///
+/// Unlike the [`BookmarkManager`] of a graph, a session only ever waits for
+/// its own latest bookmark, regardless of the database.
+/// New bookmarks are also passed on to `manager`, so that work outside of the session sees them.
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+#[derive(Debug)]
+pub(crate) struct SessionBookmarks {
+    bookmarks: Mutex<Vec<String>>,
+    manager: Option<Arc<dyn BookmarkManager>>,
+}
+
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+impl SessionBookmarks {
+    pub(crate) fn new(bookmarks: Vec<String>, manager: Option<Arc<dyn BookmarkManager>>) -> Self {
+        Self {
+            bookmarks: Mutex::new(bookmarks),
+            manager,
+        }
+    }
+
+    pub(crate) fn get(&self) -> Vec<String> {
+        self.bookmarks.lock().unwrap().clone()
+    }
+}
+
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+impl BookmarkManager for SessionBookmarks {
+    fn get_bookmarks<'a>(&'a self, _db: Option<&'a str>) -> BoxFuture<'a, Result<Vec<String>>> {
+        let bookmarks = self.get();
+        Box::pin(async move { Ok(bookmarks) })
+    }
+
+    fn update_bookmarks<'a>(
+        &'a self,
+        db: Option<&'a str>,
+        previous: &'a [String],
+        new: &'a str,
+    ) -> BoxFuture<'a, ()> {
+        // the latest bookmark already includes the state of all previous ones
+        *self.bookmarks.lock().unwrap() = vec![new.to_owned()];
+        Box::pin(async move {
+            if let Some(manager) = &self.manager {
+                manager.update_bookmarks(db, previous, new).await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn keeps_bookmarks_per_database() {
+        let manager = InMemoryBookmarkManager::new().with_bookmarks(Some("movies"), ["bm-0"]);
+
+        manager.update_bookmarks(None, &[], "bm-1").await;
+        manager.update_bookmarks(None, &[], "bm-2").await;
+        assert_eq!(manager.get_bookmarks(None).await.unwrap(), ["bm-1", "bm-2"]);
+        assert_eq!(
+            manager.get_bookmarks(Some("movies")).await.unwrap(),
+            ["bm-0"]
+        );
+
+        let previous = manager.get_bookmarks(None).await.unwrap();
+        manager.update_bookmarks(None, &previous, "bm-3").await;
+        assert_eq!(manager.get_bookmarks(None).await.unwrap(), ["bm-3"]);
+
+        // a concurrent transaction that only waited for an older bookmark keeps the newer one
+        manager.update_bookmarks(Some("movies"), &[], "bm-4").await;
+        manager
+            .update_bookmarks(Some("movies"), &["bm-0".to_owned()], "bm-5")
+            .await;
+        assert_eq!(
+            manager.get_bookmarks(Some("movies")).await.unwrap(),
+            ["bm-4", "bm-5"]
+        );
+    }
+}
@@ -1,16 +1,23 @@
-use crate::auth::{ClientCertificate, ConnectionTLSConfig};
+use crate::auth::{AuthToken, AuthTokenManager, ClientCertificate, ConnectionTLSConfig};
+use crate::bookmarks::{BookmarkManager, InMemoryBookmarkManager};
 use crate::errors::{Error, Result};
 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+use crate::routing::{
+    ClusterRoutingTableProvider, LoadBalancingStrategy, RoundRobinStrategy, RoutingTableProvider,
+    ServerAddressResolver,
+};
+use crate::types::{BoltMap, BoltType};
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
 use serde::{Deserialize, Deserializer, Serialize};
 use std::path::Path;
-use std::{ops::Deref, sync::Arc};
+use std::{ops::Deref, sync::Arc, time::Duration};
 
 const DEFAULT_FETCH_SIZE: usize = 200;
 const DEFAULT_MAX_CONNECTIONS: usize = 16;
 
 /// Newtype for the name of the database.
 /// Stores the name as an `Arc<str>` to avoid cloning the name around.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Database(Arc<str>);
 
 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
@@ -60,30 +67,164 @@ impl Deref for Database {
     }
 }
 
+/// Options for a transaction, which apply to explicit transactions
+/// as well as to the implicit transaction of a single query.
+///
+/// # Examples
+///
+/// ```
+/// use neo4rs::{BoltMap, BoltType, TransactionConfig};
+/// use std::time::Duration;
+///
+/// let mut metadata = BoltMap::default();
+/// metadata.put("app".into(), BoltType::from("reporting"));
+///
+/// let config = TransactionConfig::new()
+///     .with_timeout(Duration::from_secs(30))
+///     .with_metadata(metadata);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransactionConfig {
+    timeout: Option<Duration>,
+    metadata: Option<BoltMap>,
+}
+
+impl TransactionConfig {
+    /// Creates a config that uses the defaults of the server.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Terminates the transaction on the server if it runs longer than `timeout`.
+    ///
+    /// The timeout is sent in milliseconds, shorter timeouts are rounded up.
+    /// A timeout of zero disables the default timeout that is configured on the server.
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Self {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// Attaches metadata to the transaction,
+    /// which is shown by `SHOW TRANSACTIONS` and written to the query log.
+    pub fn with_metadata(self, metadata: BoltMap) -> Self {
+        Self {
+            metadata: Some(metadata),
+            ..self
+        }
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    pub fn metadata(&self) -> Option<&BoltMap> {
+        self.metadata.as_ref()
+    }
+
+    /// The timeout in whole milliseconds, as expected by the server.
+    pub(crate) fn timeout_millis(&self) -> Option<i64> {
+        self.timeout.map(|timeout| {
+            let millis = timeout.as_millis() + u128::from(timeout.subsec_nanos() % 1_000_000 != 0);
+            i64::try_from(millis).unwrap_or(i64::MAX)
+        })
+    }
+
+    /// Adds the `tx_timeout` and `tx_metadata` entries to the extra of a `BEGIN` or `RUN` message.
+    pub(crate) fn insert_into(&self, map: &mut BoltMap) {
+        if let Some(timeout) = self.timeout_millis() {
+            map.put("tx_timeout".into(), timeout.into());
+        }
+        if let Some(metadata) = &self.metadata {
+            map.put("tx_metadata".into(), BoltType::Map(metadata.clone()));
+        }
+    }
+}
+
+/// The limits of the connection pool, see the pool options of [`ConfigBuilder`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct PoolConfig {
+    pub(crate) acquisition_timeout: Option<Duration>,
+    pub(crate) max_lifetime: Option<Duration>,
+    pub(crate) idle_before_liveness_check: Option<Duration>,
+    pub(crate) min_idle: usize,
+}
+
+/// The timeouts and socket options for opening a connection, see the connection options of [`ConfigBuilder`].
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ConnectionConfig {
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) handshake_timeout: Option<Duration>,
+    pub(crate) hello_timeout: Option<Duration>,
+    pub(crate) keepalive: Option<Duration>,
+    pub(crate) nodelay: bool,
+}
+
+impl Default for ConnectionConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: None,
+            handshake_timeout: None,
+            hello_timeout: None,
+            keepalive: None,
+            nodelay: true,
+        }
+    }
+}
+
 /// The configuration that is used once a connection is alive.
 #[derive(Debug, Clone)]
 pub struct LiveConfig {
     pub(crate) db: Option<Database>,
     pub(crate) fetch_size: usize,
+    pub(crate) imp_user: Option<Arc<str>>,
+    pub(crate) txn_config: TransactionConfig,
+    pub(crate) bookmark_manager: Arc<dyn BookmarkManager>,
 }
 
 /// The configuration used to connect to the database, see [`crate::Graph::connect`].
 #[derive(Debug, Clone)]
 pub struct Config {
     pub(crate) uri: String,
-    pub(crate) user: String,
-    pub(crate) password: String,
+    pub(crate) auth: Arc<dyn AuthTokenManager>,
     pub(crate) max_connections: usize,
     pub(crate) db: Option<Database>,
     pub(crate) fetch_size: usize,
     pub(crate) tls_config: ConnectionTLSConfig,
+    pub(crate) connection: ConnectionConfig,
+    pub(crate) pool: PoolConfig,
+    pub(crate) bookmark_manager: Arc<dyn BookmarkManager>,
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    pub(crate) routing_table_provider: Arc<dyn RoutingTableProvider>,
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    pub(crate) load_balancing_strategy: Arc<dyn LoadBalancingStrategy>,
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    pub(crate) address_resolver: Option<Arc<dyn ServerAddressResolver>>,
+    /// The address that a router is connected to instead of the host of the uri,
+    /// which is still used to verify its certificate, see [`ServerAddressResolver`].
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    pub(crate) connect_address: Option<String>,
 }
 
 impl Config {
+    /// The uri of the Neo4j server, see [`ConfigBuilder::uri`].
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// The database that queries run on if they do not name one, see [`ConfigBuilder::db`].
+    pub fn db(&self) -> Option<&Database> {
+        self.db.as_ref()
+    }
+
     pub(crate) fn into_live_config(self) -> LiveConfig {
         LiveConfig {
             db: self.db,
             fetch_size: self.fetch_size,
+            imp_user: None,
+            txn_config: TransactionConfig::default(),
+            bookmark_manager: self.bookmark_manager,
         }
     }
 }
@@ -93,10 +234,20 @@ pub struct ConfigBuilder {
     uri: Option<String>,
     user: Option<String>,
     password: Option<String>,
+    auth: Option<Arc<dyn AuthTokenManager>>,
     db: Option<Database>,
     fetch_size: usize,
     max_connections: usize,
     tls_config: ConnectionTLSConfig,
+    connection: ConnectionConfig,
+    pool: PoolConfig,
+    bookmark_manager: Option<Arc<dyn BookmarkManager>>,
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    routing_table_provider: Option<Arc<dyn RoutingTableProvider>>,
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    load_balancing_strategy: Option<Arc<dyn LoadBalancingStrategy>>,
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    address_resolver: Option<Arc<dyn ServerAddressResolver>>,
 }
 
 impl ConfigBuilder {
@@ -123,6 +274,69 @@ impl ConfigBuilder {
         self
     }
 
+    /// The credentials for authenticating with the Neo4j server.
+    ///
+    /// Use this instead of [`ConfigBuilder::user`] and [`ConfigBuilder::password`]
+    /// to authenticate with a scheme other than `basic`, e.g. a bearer token.
+    /// Takes precedence over the user and password if both are set.
+    pub fn auth(mut self, auth: AuthToken) -> Self {
+        self.auth = Some(Arc::new(auth));
+        self
+    }
+
+    /// The manager that provides the credentials for authenticating with the Neo4j server.
+    ///
+    /// Use this for credentials that expire and need to be refreshed, e.g. bearer tokens.
+    /// Replaces any credentials set with [`ConfigBuilder::auth`],
+    /// and takes precedence over the user and password.
+    pub fn auth_manager(mut self, manager: impl AuthTokenManager + 'static) -> Self {
+        self.auth = Some(Arc::new(manager));
+        self
+    }
+
+    /// The manager that keeps track of the bookmarks of all transactions,
+    /// for causal consistency beyond a single [`crate::Graph`], e.g. across several processes.
+    ///
+    /// Defaults to an [`InMemoryBookmarkManager`] if not set.
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    pub fn bookmark_manager(mut self, manager: impl BookmarkManager + 'static) -> Self {
+        self.bookmark_manager = Some(Arc::new(manager));
+        self
+    }
+
+    /// The provider of the routing tables of a cluster, when connecting with a `neo4j` scheme.
+    ///
+    /// Defaults to a [`ClusterRoutingTableProvider`], which asks the server at the uri, if not set.
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    pub fn routing_table_provider(mut self, provider: impl RoutingTableProvider + 'static) -> Self {
+        self.routing_table_provider = Some(Arc::new(provider));
+        self
+    }
+
+    /// The strategy that selects a server of the routing table for every connection,
+    /// when connecting with a `neo4j` scheme.
+    ///
+    /// Defaults to a [`RoundRobinStrategy`] if not set,
+    /// see [`crate::LeastInFlightStrategy`] to prefer the servers with the fewest queries.
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    pub fn load_balancing_strategy(
+        mut self,
+        strategy: impl LoadBalancingStrategy + 'static,
+    ) -> Self {
+        self.load_balancing_strategy = Some(Arc::new(strategy));
+        self
+    }
+
+    /// The resolver of the address in the uri, when connecting with a `neo4j` scheme,
+    /// e.g. to expand a name of a service discovery into the addresses of several routers.
+    ///
+    /// The address is used as is if not set.
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    pub fn address_resolver(mut self, resolver: impl ServerAddressResolver + 'static) -> Self {
+        self.address_resolver = Some(Arc::new(resolver));
+        self
+    }
+
     /// The name of the database to connect to.
     ///
     /// Defaults to the server configured default database if not set.
@@ -150,6 +364,89 @@ impl ConfigBuilder {
         self
     }
 
+    /// How long to wait for the TCP connection to the server to be established,
+    /// before failing with [`Error::ConnectionTimeout`].
+    ///
+    /// Waits until the operating system gives up if not set.
+    pub fn connection_timeout(mut self, timeout: Duration) -> Self {
+        self.connection.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// How long to wait for the TLS handshake, if any, and the negotiation of the Bolt version,
+    /// before failing with [`Error::ConnectionTimeout`].
+    ///
+    /// Waits indefinitely if not set.
+    pub fn handshake_timeout(mut self, timeout: Duration) -> Self {
+        self.connection.handshake_timeout = Some(timeout);
+        self
+    }
+
+    /// How long to wait for the server to accept the credentials of a new connection
+    /// with `HELLO` and `LOGON`, before failing with [`Error::ConnectionTimeout`].
+    ///
+    /// Waits indefinitely if not set.
+    pub fn hello_timeout(mut self, timeout: Duration) -> Self {
+        self.connection.hello_timeout = Some(timeout);
+        self
+    }
+
+    /// Enables TCP keepalive, which probes connections that have been idle for `idle`,
+    /// so that connections that were dropped silently are detected by the operating system.
+    ///
+    /// Uses the default of the operating system if not set, which usually disables keepalive.
+    pub fn tcp_keepalive(mut self, idle: Duration) -> Self {
+        self.connection.keepalive = Some(idle);
+        self
+    }
+
+    /// Whether to send small messages immediately, by setting `TCP_NODELAY`,
+    /// instead of buffering them to send fewer packets.
+    ///
+    /// Defaults to `true` if not set.
+    pub fn tcp_nodelay(mut self, nodelay: bool) -> Self {
+        self.connection.nodelay = nodelay;
+        self
+    }
+
+    /// How long to wait for a connection from the pool when all connections are in use,
+    /// before failing with [`Error::ConnectionAcquisitionTimeout`].
+    ///
+    /// Waits indefinitely if not set.
+    pub fn connection_acquisition_timeout(mut self, timeout: Duration) -> Self {
+        self.pool.acquisition_timeout = Some(timeout);
+        self
+    }
+
+    /// The maximum age of a pooled connection.
+    /// Older connections are closed instead of being handed out again.
+    ///
+    /// Connections are kept for as long as they are usable if not set.
+    pub fn max_connection_lifetime(mut self, lifetime: Duration) -> Self {
+        self.pool.max_lifetime = Some(lifetime);
+        self
+    }
+
+    /// Connections that have not exchanged any message with the server for at least `idle`
+    /// are tested before they are handed out again,
+    /// and closed if the server does not answer in time.
+    ///
+    /// This detects connections that have been dropped silently, e.g. by a load balancer.
+    /// Idle connections are not tested if not set.
+    pub fn idle_time_before_liveness_check(mut self, idle: Duration) -> Self {
+        self.pool.idle_before_liveness_check = Some(idle);
+        self
+    }
+
+    /// The number of idle connections that the pool keeps open in addition to the ones
+    /// in use, up to [`ConfigBuilder::max_connections`].
+    ///
+    /// Defaults to 0 if not set, which opens connections only when they are needed.
+    pub fn min_idle_connections(mut self, min_idle: usize) -> Self {
+        self.pool.min_idle = min_idle;
+        self
+    }
+
     /// A CA certificate to use to validate the server's certificate.
     ///
     /// This is required if the server's certificate is not signed by a known CA.
@@ -167,15 +464,38 @@ impl ConfigBuilder {
     }
 
     pub fn build(self) -> Result<Config> {
-        if let (Some(uri), Some(user), Some(password)) = (self.uri, self.user, self.password) {
+        let auth = match (self.auth, self.user, self.password) {
+            (Some(auth), _, _) => Some(auth),
+            (None, Some(user), Some(password)) => {
+                Some(Arc::new(AuthToken::basic(user, password)) as Arc<dyn AuthTokenManager>)
+            }
+            _ => None,
+        };
+        if let (Some(uri), Some(auth)) = (self.uri, auth) {
             Ok(Config {
                 uri,
-                user,
-                password,
+                auth,
                 fetch_size: self.fetch_size,
                 max_connections: self.max_connections,
                 db: self.db,
                 tls_config: self.tls_config,
+                connection: self.connection,
+                pool: self.pool,
+                bookmark_manager: self
+                    .bookmark_manager
+                    .unwrap_or_else(|| Arc::new(InMemoryBookmarkManager::new())),
+                #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+                routing_table_provider: self
+                    .routing_table_provider
+                    .unwrap_or_else(|| Arc::new(ClusterRoutingTableProvider)),
+                #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+                load_balancing_strategy: self
+                    .load_balancing_strategy
+                    .unwrap_or_else(|| Arc::new(RoundRobinStrategy::default())),
+                #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+                address_resolver: self.address_resolver,
+                #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+                connect_address: None,
             })
         } else {
             Err(Error::InvalidConfig)
@@ -189,10 +509,20 @@ impl Default for ConfigBuilder {
             uri: None,
             user: None,
             password: None,
+            auth: None,
             db: None,
             max_connections: DEFAULT_MAX_CONNECTIONS,
             fetch_size: DEFAULT_FETCH_SIZE,
             tls_config: ConnectionTLSConfig::None,
+            connection: ConnectionConfig::default(),
+            pool: PoolConfig::default(),
+            bookmark_manager: None,
+            #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+            routing_table_provider: None,
+            #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+            load_balancing_strategy: None,
+            #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+            address_resolver: None,
         }
     }
 }
@@ -201,6 +531,10 @@ impl Default for ConfigBuilder {
 mod tests {
     use super::*;
 
+    fn token(config: &Config) -> AuthToken {
+        futures::executor::block_on(config.auth.get_token()).unwrap()
+    }
+
     #[test]
     fn should_build_config() {
         let config = ConfigBuilder::default()
@@ -213,8 +547,10 @@ mod tests {
             .build()
             .unwrap();
         assert_eq!(config.uri, "127.0.0.1:7687");
-        assert_eq!(config.user, "some_user");
-        assert_eq!(config.password, "some_password");
+        assert_eq!(
+            token(&config),
+            AuthToken::basic("some_user", "some_password")
+        );
         assert_eq!(config.db.as_deref(), Some("some_db"));
         assert_eq!(config.fetch_size, 10);
         assert_eq!(config.max_connections, 5);
@@ -230,12 +566,72 @@ mod tests {
             .build()
             .unwrap();
         assert_eq!(config.uri, "127.0.0.1:7687");
-        assert_eq!(config.user, "some_user");
-        assert_eq!(config.password, "some_password");
+        assert_eq!(
+            token(&config),
+            AuthToken::basic("some_user", "some_password")
+        );
         assert_eq!(config.db, None);
         assert_eq!(config.fetch_size, 200);
         assert_eq!(config.max_connections, 16);
         assert_eq!(config.tls_config, ConnectionTLSConfig::None);
+        assert_eq!(config.pool, PoolConfig::default());
+    }
+
+    #[test]
+    fn should_build_with_pool_options() {
+        let config = ConfigBuilder::default()
+            .uri("127.0.0.1:7687")
+            .user("some_user")
+            .password("some_password")
+            .connection_acquisition_timeout(Duration::from_secs(5))
+            .max_connection_lifetime(Duration::from_secs(3600))
+            .idle_time_before_liveness_check(Duration::from_secs(60))
+            .min_idle_connections(2)
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.pool,
+            PoolConfig {
+                acquisition_timeout: Some(Duration::from_secs(5)),
+                max_lifetime: Some(Duration::from_secs(3600)),
+                idle_before_liveness_check: Some(Duration::from_secs(60)),
+                min_idle: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn should_build_with_connection_options() {
+        let config = ConfigBuilder::default()
+            .uri("127.0.0.1:7687")
+            .user("some_user")
+            .password("some_password")
+            .build()
+            .unwrap();
+        assert_eq!(config.connection, ConnectionConfig::default());
+        assert!(config.connection.nodelay);
+
+        let config = ConfigBuilder::default()
+            .uri("127.0.0.1:7687")
+            .user("some_user")
+            .password("some_password")
+            .connection_timeout(Duration::from_secs(1))
+            .handshake_timeout(Duration::from_secs(2))
+            .hello_timeout(Duration::from_secs(3))
+            .tcp_keepalive(Duration::from_secs(60))
+            .tcp_nodelay(false)
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.connection,
+            ConnectionConfig {
+                connect_timeout: Some(Duration::from_secs(1)),
+                handshake_timeout: Some(Duration::from_secs(2)),
+                hello_timeout: Some(Duration::from_secs(3)),
+                keepalive: Some(Duration::from_secs(60)),
+                nodelay: false,
+            }
+        );
     }
 
     #[test]
@@ -248,14 +644,48 @@ mod tests {
             .build()
             .unwrap();
         assert_eq!(config.uri, "127.0.0.1:7687");
-        assert_eq!(config.user, "some_user");
-        assert_eq!(config.password, "some_password");
+        assert_eq!(
+            token(&config),
+            AuthToken::basic("some_user", "some_password")
+        );
         assert_eq!(config.db, None);
         assert_eq!(config.fetch_size, 200);
         assert_eq!(config.max_connections, 16);
         assert_eq!(config.tls_config, ConnectionTLSConfig::NoSSLValidation);
     }
 
+    #[test]
+    fn should_build_with_auth_token() {
+        let config = ConfigBuilder::default()
+            .uri("127.0.0.1:7687")
+            .auth(AuthToken::bearer("some_token"))
+            .build()
+            .unwrap();
+        assert_eq!(token(&config), AuthToken::bearer("some_token"));
+
+        let config = ConfigBuilder::default()
+            .uri("127.0.0.1:7687")
+            .user("some_user")
+            .password("some_password")
+            .auth(AuthToken::none())
+            .build()
+            .unwrap();
+        assert_eq!(token(&config), AuthToken::none());
+    }
+
+    #[test]
+    fn should_build_with_auth_manager() {
+        let config = ConfigBuilder::default()
+            .uri("127.0.0.1:7687")
+            .auth(AuthToken::none())
+            .auth_manager(crate::RefreshingAuthTokenManager::new(|| async {
+                Ok(AuthToken::bearer("some_token"))
+            }))
+            .build()
+            .unwrap();
+        assert_eq!(token(&config), AuthToken::bearer("some_token"));
+    }
+
     #[test]
     fn should_reject_invalid_config() {
         assert!(ConfigBuilder::default()
@@ -276,4 +706,32 @@ mod tests {
             .build()
             .is_err());
     }
+
+    #[test]
+    fn should_round_up_transaction_timeout() {
+        let timeout = |timeout| {
+            TransactionConfig::new()
+                .with_timeout(timeout)
+                .timeout_millis()
+        };
+        assert_eq!(TransactionConfig::new().timeout_millis(), None);
+        assert_eq!(timeout(Duration::ZERO), Some(0));
+        assert_eq!(timeout(Duration::from_secs(2)), Some(2000));
+        assert_eq!(timeout(Duration::from_micros(1500)), Some(2));
+    }
+
+    #[test]
+    fn should_insert_transaction_config() {
+        let metadata = [("app".into(), "reporting".into())].into_iter().collect();
+        let config = TransactionConfig::new()
+            .with_timeout(Duration::from_secs(1))
+            .with_metadata(metadata);
+
+        let mut extra = BoltMap::default();
+        config.insert_into(&mut extra);
+
+        assert_eq!(extra.get::<i64>("tx_timeout").unwrap(), 1000);
+        let metadata = extra.get::<BoltMap>("tx_metadata").unwrap();
+        assert_eq!(metadata.get::<String>("app").unwrap(), "reporting");
+    }
 }
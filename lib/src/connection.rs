@@ -1,24 +1,28 @@
-use crate::auth::ConnectionTLSConfig;
+use crate::auth::{AuthToken, AuthTokenManager, ConnectionTLSConfig};
 #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
 use crate::messages::HelloBuilder;
 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
 use {
     crate::bolt::{
-        ConnectionsHints, ExpectedResponse, Hello, HelloBuilder, Message, MessageResponse, Reset,
-        Summary,
+        ExpectedResponse, Goodbye, Hello, HelloBuilder, Logoff, Logon, Message, MessageResponse,
+        Reset, Summary,
     },
     log::debug,
 };
 
-#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
-use crate::routing::{Route, RoutingTable};
 use crate::{
+    config::ConnectionConfig,
     connection::stream::ConnectionStream,
     errors::{Error, Result},
     messages::{BoltRequest, BoltResponse},
     version::Version,
     BoltMap, BoltString, BoltType,
 };
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+use crate::{
+    routing::{Route, RoutingTable},
+    Config,
+};
 use bytes::{BufMut, Bytes, BytesMut};
 use log::{info, warn};
 use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
@@ -26,9 +30,16 @@ use rustls::crypto::CryptoProvider;
 use rustls::pki_types::{CertificateDer, UnixTime};
 use rustls::{DigitallySignedStruct, SignatureScheme};
 use std::fmt::{Debug, Display, Formatter};
-use std::{fs::File, io::BufReader, mem, sync::Arc};
+use std::{
+    fs::File,
+    future::Future,
+    io::BufReader,
+    mem,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::{
-    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufStream},
+    io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufStream},
     net::TcpStream,
 };
 use tokio_rustls::{
@@ -42,20 +53,63 @@ use url::{Host, Url};
 
 const MAX_CHUNK_SIZE: usize = 65_535 - mem::size_of::<u16>();
 
+/// The signature of a `RECORD`, the only response that is not a summary.
+const RECORD: u8 = 0x71;
+
+/// How long the server may take to acknowledge the `RESET` after an operation was interrupted.
+#[cfg(not(test))]
+const INTERRUPT_TIMEOUT: Duration = Duration::from_secs(5);
+#[cfg(test)]
+const INTERRUPT_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Information about a server that the driver connected to,
+/// see [`crate::Graph::server_info`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerInfo {
+    /// The address of the server, e.g. `bolt://localhost:7687`.
+    pub address: String,
+    /// The product and version of the server, e.g. `Neo4j/5.0.0`.
+    pub agent: String,
+    /// The version of the Bolt protocol that was negotiated with the server.
+    pub protocol_version: Version,
+    /// The id that the server assigned to the connection, e.g. `bolt-42`.
+    pub connection_id: String,
+}
+
 #[derive(Debug)]
 pub struct Connection {
     version: Version,
     stream: BufStream<ConnectionStream>,
-    #[allow(unused)]
-    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
-    hints: Option<ConnectionsHints>,
+    auth: Option<AuthToken>,
+    agent: String,
+    connection_id: String,
+    recv_timeout: Option<Duration>,
+    defunct: bool,
+    /// The number of requests that have been sent, but whose summary has not been received yet.
+    pending: usize,
+    /// Whether a message is partially sent or received,
+    /// which leaves the stream in an unknown state if the operation is abandoned.
+    mid_message: bool,
+    /// When a message was last sent or received.
+    last_activity: Instant,
+    created: Instant,
 }
 
 impl Connection {
     pub(crate) async fn new(info: &ConnectionInfo) -> Result<Self> {
         let mut connection = Self::prepare(&info.prepare).await?;
-        let hello = info.init.to_hello(connection.version);
-        connection.hello(hello).await?;
+        let auth = info.init.auth.get_token().await?;
+        with_timeout(info.init.hello_timeout, "authenticating", async {
+            let hello = info.init.to_hello(&auth, connection.version);
+            connection.hello(hello).await?;
+            if connection.version >= Version::V5_1 {
+                let logon = InitOpts::to_logon(&auth);
+                connection.logon(logon).await?;
+            }
+            Ok(())
+        })
+        .await?;
+        connection.auth = Some(auth);
         Ok(connection)
     }
 
@@ -63,24 +117,117 @@ impl Connection {
         self.version
     }
 
+    /// Impersonation requires Bolt 4.4 or later.
+    pub(crate) fn check_impersonation(&self) -> Result<()> {
+        if self.version < Version::V4_4 {
+            return Err(Error::ImpersonationNotSupported(self.version));
+        }
+        Ok(())
+    }
+
+    /// The server and connection details that were sent in the response to `HELLO`.
+    pub(crate) fn server_info(&self, address: &str) -> ServerInfo {
+        ServerInfo {
+            address: address.to_owned(),
+            agent: self.agent.clone(),
+            protocol_version: self.version,
+            connection_id: self.connection_id.clone(),
+        }
+    }
+
+    /// How long ago the connection has been opened.
+    pub(crate) fn age(&self) -> Duration {
+        self.created.elapsed()
+    }
+
+    /// How long the connection has not exchanged any message with the server.
+    pub(crate) fn idle_time(&self) -> Duration {
+        self.last_activity.elapsed()
+    }
+
+    /// Whether the server did not send anything for longer than the receive timeout
+    /// from its hints, or an operation was abandoned in the middle of a message,
+    /// in which case the connection must not be used again.
+    pub(crate) fn is_defunct(&self) -> bool {
+        self.defunct || self.mid_message
+    }
+
+    /// Recovers the connection after an operation on it has been abandoned, e.g. a query that timed out.
+    ///
+    /// The server stops working on the outstanding requests once it receives a `RESET`.
+    /// A connection whose server does not acknowledge the `RESET` in time is marked as defunct.
+    pub(crate) async fn interrupt(&mut self) {
+        if !self.is_defunct() {
+            match tokio::time::timeout(INTERRUPT_TIMEOUT, self.reset()).await {
+                Ok(Ok(())) => return,
+                Ok(Err(e)) => warn!("Failed to reset the connection after an interruption: {e}"),
+                Err(_) => {
+                    warn!("The server did not acknowledge the reset within {INTERRUPT_TIMEOUT:?}")
+                }
+            }
+        }
+        self.defunct = true;
+    }
+
+    /// The token that this connection has been authenticated with.
+    pub(crate) fn auth(&self) -> Option<&AuthToken> {
+        self.auth.as_ref()
+    }
+
+    /// Authenticates this connection with a different token, using `LOGOFF` and `LOGON`.
+    /// Re-authentication requires Bolt 5.1 or later.
+    pub(crate) async fn reauth(&mut self, auth: &AuthToken) -> Result<()> {
+        if self.version < Version::V5_1 {
+            return Err(Error::AuthenticationError(format!(
+                "switching the user of a connection requires Bolt 5.1 or later, but the server uses Bolt {}",
+                self.version
+            )));
+        }
+        // a connection that fails or is abandoned before it is logged on again must not be reused
+        self.defunct = true;
+        if self.auth.is_some() {
+            self.logoff().await?;
+            self.auth = None;
+        }
+        self.logon(InitOpts::to_logon(auth)).await?;
+        self.auth = Some(auth.clone());
+        self.defunct = false;
+        Ok(())
+    }
+
     pub(crate) async fn prepare(opts: &PrepareOpts) -> Result<Self> {
-        let mut stream = match &opts.host {
-            Host::Domain(domain) => TcpStream::connect((&**domain, opts.port)).await?,
-            Host::Ipv4(ip) => TcpStream::connect((*ip, opts.port)).await?,
-            Host::Ipv6(ip) => TcpStream::connect((*ip, opts.port)).await?,
+        let connect = async {
+            Ok(match &opts.host {
+                Host::Domain(domain) => TcpStream::connect((&**domain, opts.port)).await?,
+                Host::Ipv4(ip) => TcpStream::connect((*ip, opts.port)).await?,
+                Host::Ipv6(ip) => TcpStream::connect((*ip, opts.port)).await?,
+            })
         };
+        let mut stream = with_timeout(opts.connect_timeout, "connecting", connect).await?;
+        stream.set_nodelay(opts.nodelay)?;
+        if let Some(idle) = opts.keepalive {
+            let keepalive = socket2::TcpKeepalive::new().with_time(idle);
+            socket2::SockRef::from(&stream).set_tcp_keepalive(&keepalive)?;
+        }
 
-        Ok(match &opts.encryption {
-            Some((connector, domain)) => {
-                let mut stream = connector.connect(domain.clone(), stream).await?;
-                let version = Self::init(&mut stream).await?;
-                Self::create(stream, version)
-            }
-            None => {
-                let version = Self::init(&mut stream).await?;
-                Self::create(stream, version)
-            }
-        })
+        with_timeout(
+            opts.handshake_timeout,
+            "negotiating the Bolt version",
+            async {
+                Ok(match &opts.encryption {
+                    Some((connector, domain)) => {
+                        let mut stream = connector.connect(domain.clone(), stream).await?;
+                        let version = Self::init(&mut stream).await?;
+                        Self::create(stream, version)
+                    }
+                    None => {
+                        let version = Self::init(&mut stream).await?;
+                        Self::create(stream, version)
+                    }
+                })
+            },
+        )
+        .await
     }
 
     async fn init<A: AsyncWrite + AsyncRead + Unpin>(stream: &mut A) -> Result<Version> {
@@ -89,17 +236,73 @@ impl Connection {
 
         let mut response = [0, 0, 0, 0];
         stream.read_exact(&mut response).await?;
-        let version = Version::parse(response)?;
+        let version = if response == Version::MANIFEST_V1 {
+            Self::negotiate_manifest(stream).await?
+        } else {
+            Version::parse(response)?
+        };
         info!("Connected to Neo4j with version {version}");
         Ok(version)
     }
 
+    /// Handles the manifest v1 handshake: the server lists all its versions
+    /// and capabilities and the client answers with the version it picked.
+    async fn negotiate_manifest<A: AsyncWrite + AsyncRead + Unpin>(
+        stream: &mut A,
+    ) -> Result<Version> {
+        let count = Self::read_varint(stream).await?;
+        let mut offered = Vec::with_capacity(count.min(16) as usize);
+        for _ in 0..count {
+            let mut version = [0, 0, 0, 0];
+            stream.read_exact(&mut version).await?;
+            offered.push(version);
+        }
+        // we do not make use of any of the capabilities yet
+        let _capabilities = Self::read_varint(stream).await?;
+
+        let Some(version) = Version::select(&offered) else {
+            stream.write_all(&[0, 0, 0, 0]).await?;
+            stream.flush().await?;
+            return Err(match offered.iter().max_by_key(|v| (v[3], v[2])) {
+                Some(&[_, _, minor, major]) => Error::UnsupportedVersion(major, minor),
+                None => Error::ProtocolMismatch(u32::from_be_bytes(Version::MANIFEST_V1)),
+            });
+        };
+
+        stream.write_all(&version.to_bytes()).await?;
+        // no capabilities requested
+        stream.write_u8(0).await?;
+        stream.flush().await?;
+        Ok(version)
+    }
+
+    async fn read_varint<A: AsyncRead + Unpin>(stream: &mut A) -> Result<u64> {
+        let mut value = 0;
+        for shift in (0..u64::BITS).step_by(7) {
+            let byte = stream.read_u8().await?;
+            value |= u64::from(byte & 0x7F) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(Error::UnexpectedMessage(
+            "invalid variable length integer in the handshake manifest".into(),
+        ))
+    }
+
     fn create(stream: impl Into<ConnectionStream>, version: Version) -> Connection {
         Connection {
             version,
             stream: BufStream::new(stream.into()),
-            #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
-            hints: None,
+            auth: None,
+            agent: String::new(),
+            connection_id: String::new(),
+            recv_timeout: None,
+            defunct: false,
+            pending: 0,
+            mid_message: false,
+            last_activity: Instant::now(),
+            created: Instant::now(),
         }
     }
 
@@ -113,10 +316,19 @@ impl Connection {
     #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
     async fn hello(&mut self, req: BoltRequest) -> Result<()> {
         match self.send_recv(req).await? {
-            BoltResponse::Success(_msg) => Ok(()),
-            BoltResponse::Failure(msg) => {
-                Err(Error::AuthenticationError(msg.get("message").unwrap()))
+            BoltResponse::Success(msg) => {
+                self.agent = msg.get("server").unwrap_or_default();
+                self.connection_id = msg.get("connection_id").unwrap_or_default();
+                self.recv_timeout = msg
+                    .get::<BoltMap>("hints")
+                    .and_then(|hints| hints.get::<i64>("connection.recv_timeout_seconds"))
+                    .ok()
+                    .and_then(|seconds| u64::try_from(seconds).ok())
+                    .filter(|&seconds| seconds > 0)
+                    .map(Duration::from_secs);
+                Ok(())
             }
+            BoltResponse::Failure(f) => Err(Error::Neo4j(f.into_error())),
             msg => Err(msg.into_error("HELLO")),
         }
     }
@@ -127,11 +339,49 @@ impl Connection {
 
         match hello {
             Summary::Success(msg) => {
-                self.hints = msg.metadata.hints;
+                self.agent = msg.metadata.server;
+                self.connection_id = msg.metadata.connection_id;
+                self.recv_timeout = msg.metadata.hints.and_then(|hints| hints.recv_timeout());
                 Ok(())
             }
             Summary::Ignored => Err(Error::RequestIgnoredError),
-            Summary::Failure(msg) => Err(Error::AuthenticationError(msg.message)),
+            Summary::Failure(f) => Err(f.into_error()),
+        }
+    }
+
+    #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+    async fn logon(&mut self, req: BoltRequest) -> Result<()> {
+        match self.send_recv(req).await? {
+            BoltResponse::Success(_msg) => Ok(()),
+            BoltResponse::Failure(f) => Err(Error::Neo4j(f.into_error())),
+            msg => Err(msg.into_error("LOGON")),
+        }
+    }
+
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    async fn logon(&mut self, logon: Logon<'_>) -> Result<()> {
+        match self.send_recv_as(logon).await? {
+            Summary::Success(_) => Ok(()),
+            Summary::Ignored => Err(Error::RequestIgnoredError),
+            Summary::Failure(f) => Err(f.into_error()),
+        }
+    }
+
+    #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+    async fn logoff(&mut self) -> Result<()> {
+        match self.send_recv(BoltRequest::logoff()).await? {
+            BoltResponse::Success(_) => Ok(()),
+            BoltResponse::Failure(f) => Err(Error::Neo4j(f.into_error())),
+            msg => Err(msg.into_error("LOGOFF")),
+        }
+    }
+
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    async fn logoff(&mut self) -> Result<()> {
+        match self.send_recv_as(Logoff).await? {
+            Summary::Success(_) => Ok(()),
+            Summary::Ignored => Err(Error::RequestIgnoredError),
+            Summary::Failure(f) => Err(f.into_error()),
         }
     }
 
@@ -150,7 +400,9 @@ impl Connection {
     pub async fn reset(&mut self) -> Result<()> {
         #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
         {
-            match self.send_recv(BoltRequest::reset()).await? {
+            self.send(BoltRequest::reset()).await?;
+            self.skip_pending().await?;
+            match self.recv().await? {
                 BoltResponse::Success(_) => Ok(()),
                 BoltResponse::Failure(f) => Err(Error::Neo4j(f.into_error())),
                 msg => Err(msg.into_error("RESET")),
@@ -159,7 +411,12 @@ impl Connection {
 
         #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
         {
-            match self.send_recv_as(Reset).await? {
+            self.send_as(Reset).await?;
+            self.skip_pending().await?;
+            match self
+                .recv_as::<<Reset as ExpectedResponse>::Response>()
+                .await?
+            {
                 Summary::Success(_) => Ok(()),
                 Summary::Failure(err) => Err(Error::ConnectionClosed(err)),
                 msg => Err(Error::UnexpectedMessage(format!(
@@ -170,6 +427,25 @@ impl Connection {
         }
     }
 
+    /// Skips the responses to all but the last request,
+    /// e.g. the ones that were abandoned when a query was interrupted.
+    async fn skip_pending(&mut self) -> Result<()> {
+        while self.pending > 1 {
+            self.recv_bytes().await?;
+        }
+        Ok(())
+    }
+
+    /// Tells the server that the connection is no longer used, and closes it.
+    pub(crate) async fn goodbye(mut self) -> Result<()> {
+        #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+        self.send(BoltRequest::goodbye()).await?;
+        #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+        self.send_as(Goodbye).await?;
+        self.stream.shutdown().await?;
+        Ok(())
+    }
+
     pub async fn send_recv(&mut self, message: BoltRequest) -> Result<BoltResponse> {
         self.send(message).await?;
         self.recv().await
@@ -212,26 +488,51 @@ impl Connection {
     async fn send_bytes(&mut self, bytes: Bytes) -> Result<()> {
         Self::dbg("send", &bytes);
         let end_marker: [u8; 2] = [0, 0];
+        self.mid_message = true;
         for c in bytes.chunks(MAX_CHUNK_SIZE) {
             self.stream.write_u16(c.len() as u16).await?;
             self.stream.write_all(c).await?;
         }
         self.stream.write_all(&end_marker).await?;
         self.stream.flush().await?;
+        self.mid_message = false;
+        self.pending += 1;
+        self.last_activity = Instant::now();
         Ok(())
     }
 
     async fn recv_bytes(&mut self) -> Result<Bytes> {
+        let bytes = self.recv_message().await;
+        if let Err(Error::ConnectionTimeout(_, timeout)) = bytes {
+            // the connection may be half-open, and the stream is left in an unknown state
+            warn!("The server did not send anything for {timeout:?}, closing the connection");
+            self.defunct = true;
+        }
+        if let Ok(bytes) = &bytes {
+            self.last_activity = Instant::now();
+            if bytes.get(1) != Some(&RECORD) {
+                self.pending = self.pending.saturating_sub(1);
+            }
+        }
+        bytes
+    }
+
+    async fn recv_message(&mut self) -> Result<Bytes> {
         let mut bytes = BytesMut::new();
         let mut chunk_size = 0;
         while chunk_size == 0 {
+            // waiting for the next message can be abandoned without losing any data
+            with_recv_timeout(self.recv_timeout, self.stream.fill_buf()).await?;
+            self.mid_message = true;
             chunk_size = self.read_chunk_size().await?;
+            self.mid_message = chunk_size > 0;
         }
 
         while chunk_size > 0 {
             self.read_chunk(chunk_size, &mut bytes).await?;
             chunk_size = self.read_chunk_size().await?;
         }
+        self.mid_message = false;
 
         let bytes = bytes.freeze();
         Self::dbg("recv", &bytes);
@@ -239,7 +540,8 @@ impl Connection {
     }
 
     async fn read_chunk_size(&mut self) -> Result<usize> {
-        Ok(usize::from(self.stream.read_u16().await?))
+        let size = with_recv_timeout(self.recv_timeout, self.stream.read_u16()).await?;
+        Ok(usize::from(size))
     }
 
     async fn read_chunk(&mut self, chunk_size: usize, buf: &mut BytesMut) -> Result<()> {
@@ -249,10 +551,8 @@ impl Connection {
         }
         let mut remaining = chunk_size;
         while remaining > 0 {
-            remaining -= (&mut self.stream)
-                .take(remaining as u64)
-                .read_buf(buf)
-                .await?;
+            let mut chunk = (&mut self.stream).take(remaining as u64);
+            remaining -= with_recv_timeout(self.recv_timeout, chunk.read_buf(buf)).await?;
         }
         Ok(())
     }
@@ -302,11 +602,38 @@ impl Display for Routing {
     }
 }
 
+/// Fails with [`Error::ConnectionTimeout`] if `future` does not complete within `timeout`.
+async fn with_timeout<T>(
+    timeout: Option<Duration>,
+    stage: &'static str,
+    future: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, future)
+            .await
+            .map_err(|_| Error::ConnectionTimeout(stage, timeout))?,
+        None => future.await,
+    }
+}
+
+/// Fails with [`Error::ConnectionTimeout`] if the server does not send anything within `timeout`,
+/// the receive timeout that the server sent in its hints.
+async fn with_recv_timeout<T>(
+    timeout: Option<Duration>,
+    read: impl Future<Output = std::io::Result<T>>,
+) -> Result<T> {
+    with_timeout(timeout, "waiting for the server", async { Ok(read.await?) }).await
+}
+
 #[derive(Clone)]
 pub(crate) struct PrepareOpts {
     pub(crate) host: Host<Arc<str>>,
     pub(crate) port: u16,
     pub(crate) encryption: Option<(TlsConnector, ServerName<'static>)>,
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) handshake_timeout: Option<Duration>,
+    pub(crate) keepalive: Option<Duration>,
+    pub(crate) nodelay: bool,
 }
 
 impl Debug for PrepareOpts {
@@ -315,40 +642,34 @@ impl Debug for PrepareOpts {
             .field("host", &self.host)
             .field("port", &self.port)
             .field("encryption", &self.encryption.is_some())
+            .field("connect_timeout", &self.connect_timeout)
+            .field("handshake_timeout", &self.handshake_timeout)
+            .field("keepalive", &self.keepalive)
+            .field("nodelay", &self.nodelay)
             .finish()
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub(crate) struct InitOpts {
-    pub(crate) user: Arc<str>,
-    pub(crate) password: Arc<str>,
+    pub(crate) auth: Arc<dyn AuthTokenManager>,
     pub(crate) routing: Routing,
-}
-
-impl Debug for InitOpts {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("InitOpts")
-            .field("user", &self.user)
-            .field("password", &"***")
-            .field("routing", &self.routing)
-            .finish()
-    }
+    pub(crate) hello_timeout: Option<Duration>,
 }
 
 impl InitOpts {
     #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
-    pub(crate) fn to_hello(&self, version: Version) -> BoltRequest {
-        HelloBuilder::new(&*self.user, &*self.password)
+    pub(crate) fn to_hello(&self, auth: &AuthToken, version: Version) -> BoltRequest {
+        HelloBuilder::new(auth)
             .with_routing(self.routing.clone())
             .build(version)
     }
 
     #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
-    pub(crate) fn to_hello(&self, version: Version) -> Hello {
+    pub(crate) fn to_hello<'a>(&'a self, auth: &'a AuthToken, version: Version) -> Hello<'a> {
         match self.routing {
-            Routing::No => HelloBuilder::new(&self.user, &self.password).build(version),
-            Routing::Yes(ref routing) => HelloBuilder::new(&self.user, &self.password)
+            Routing::No => HelloBuilder::from_auth(auth).build(version),
+            Routing::Yes(ref routing) => HelloBuilder::from_auth(auth)
                 .with_routing(
                     routing
                         .iter()
@@ -357,6 +678,16 @@ impl InitOpts {
                 .build(version),
         }
     }
+
+    #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+    pub(crate) fn to_logon(auth: &AuthToken) -> BoltRequest {
+        BoltRequest::logon(auth)
+    }
+
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    pub(crate) fn to_logon(auth: &AuthToken) -> Logon<'_> {
+        Logon::new(auth)
+    }
 }
 
 #[derive(Clone)]
@@ -368,8 +699,7 @@ pub(crate) struct ConnectionInfo {
 impl Debug for ConnectionInfo {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ConnectionInfo")
-            .field("user", &self.init.user)
-            .field("password", &"***")
+            .field("auth", &self.init.auth)
             .field("host", &self.prepare.host)
             .field("port", &self.prepare.port)
             .field("routing", &self.init.routing)
@@ -381,9 +711,9 @@ impl Debug for ConnectionInfo {
 impl ConnectionInfo {
     pub(crate) fn new(
         uri: &str,
-        user: &str,
-        password: &str,
+        auth: Arc<dyn AuthTokenManager>,
         tls_config: &ConnectionTLSConfig,
+        connection: &ConnectionConfig,
     ) -> Result<Self> {
         let mut url = NeoUrl::parse(uri)?;
 
@@ -422,27 +752,54 @@ impl ConnectionInfo {
 
         url.warn_on_unexpected_components();
 
-        let host = match url.host() {
-            Host::Domain(s) => Host::Domain(Arc::<str>::from(s)),
-            Host::Ipv4(d) => Host::Ipv4(d),
-            Host::Ipv6(d) => Host::Ipv6(d),
-        };
-
         let prepare = PrepareOpts {
-            host,
+            host: Self::owned_host(url.host()),
             port: url.port(),
             encryption,
+            connect_timeout: connection.connect_timeout,
+            handshake_timeout: connection.handshake_timeout,
+            keepalive: connection.keepalive,
+            nodelay: connection.nodelay,
         };
 
         let init = InitOpts {
-            user: user.into(),
-            password: password.into(),
+            auth,
             routing,
+            hello_timeout: connection.hello_timeout,
         };
 
         Ok(Self { prepare, init })
     }
 
+    /// The connection info of the router at the uri of `config`, which is connected to
+    /// at the address it has been resolved to, if any.
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    pub(crate) fn for_router(config: &Config, auth: Arc<dyn AuthTokenManager>) -> Result<Self> {
+        let info = Self::new(&config.uri, auth, &config.tls_config, &config.connection)?;
+        match &config.connect_address {
+            Some(address) => info.connect_to(address),
+            None => Ok(info),
+        }
+    }
+
+    /// Connects to `address` instead of the host of the uri, which is still used
+    /// to verify the certificate of the server and as the address of the routing context.
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    pub(crate) fn connect_to(mut self, address: &str) -> Result<Self> {
+        let url = NeoUrl::parse(address)?;
+        self.prepare.host = Self::owned_host(url.host());
+        self.prepare.port = url.port();
+        Ok(self)
+    }
+
+    fn owned_host(host: Host<&str>) -> Host<Arc<str>> {
+        match host {
+            Host::Domain(s) => Host::Domain(Arc::<str>::from(s)),
+            Host::Ipv4(d) => Host::Ipv4(d),
+            Host::Ipv6(d) => Host::Ipv6(d),
+        }
+    }
+
     fn tls_connector(
         host: Host<&str>,
         tls_config: &ConnectionTLSConfig,
@@ -706,9 +1063,172 @@ impl ServerCertVerifier for NoCertificateVerification {
 
 #[cfg(test)]
 mod tests {
+    use std::{sync::Arc, time::Duration};
+    use tokio::{
+        io::{duplex, AsyncReadExt, AsyncWriteExt, DuplexStream},
+        net::TcpListener,
+    };
     use url::Host;
 
-    use super::NeoUrl;
+    use super::{Connection, ConnectionInfo, NeoUrl};
+    use crate::{
+        auth::{AuthToken, ConnectionTLSConfig},
+        config::ConnectionConfig,
+        errors::Error,
+        version::Version,
+    };
+
+    /// A server that accepts connections and answers the handshake with `version`, if any,
+    /// but never sends anything else.
+    async fn unresponsive_server(version: Option<Version>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let mut handshake = [0; 20];
+                    stream.read_exact(&mut handshake).await?;
+                    if let Some(version) = version {
+                        stream.write_all(&version.to_bytes()).await?;
+                    }
+                    // keep the connection open until the client gives up
+                    tokio::io::copy(&mut stream, &mut tokio::io::sink()).await
+                });
+            }
+        });
+        format!("bolt://{addr}")
+    }
+
+    async fn connect(uri: &str, connection: ConnectionConfig) -> Result<Connection, Error> {
+        let info = ConnectionInfo::new(
+            uri,
+            Arc::new(AuthToken::basic("neo4j", "neo4j")),
+            &ConnectionTLSConfig::None,
+            &connection,
+        )?;
+        Connection::new(&info).await
+    }
+
+    #[tokio::test]
+    async fn should_time_out_during_handshake() {
+        let uri = unresponsive_server(None).await;
+        let connection = ConnectionConfig {
+            handshake_timeout: Some(Duration::from_millis(50)),
+            ..Default::default()
+        };
+
+        let error = connect(&uri, connection).await.unwrap_err();
+        assert!(matches!(
+            error,
+            Error::ConnectionTimeout("negotiating the Bolt version", timeout)
+                if timeout == Duration::from_millis(50)
+        ));
+        assert!(error.can_retry());
+    }
+
+    #[tokio::test]
+    async fn should_time_out_during_hello() {
+        let uri = unresponsive_server(Some(Version::V4_4)).await;
+        let connection = ConnectionConfig {
+            handshake_timeout: Some(Duration::from_secs(5)),
+            hello_timeout: Some(Duration::from_millis(50)),
+            keepalive: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+
+        let error = connect(&uri, connection).await.unwrap_err();
+        assert!(matches!(
+            error,
+            Error::ConnectionTimeout("authenticating", _)
+        ));
+    }
+
+    async fn read_client_handshake(server: &mut DuplexStream) {
+        let mut handshake = [0; 20];
+        server.read_exact(&mut handshake).await.unwrap();
+        assert_eq!(&handshake[..4], &[0x60, 0x60, 0xB0, 0x17]);
+        assert_eq!(&handshake[4..8], &Version::MANIFEST_V1);
+    }
+
+    #[tokio::test]
+    async fn should_negotiate_version_with_manifest() {
+        let (mut client, mut server) = duplex(64);
+
+        let server = tokio::spawn(async move {
+            read_client_handshake(&mut server).await;
+            server.write_all(&Version::MANIFEST_V1).await.unwrap();
+            // two entries: 5.8 down to 5.0 and 4.4
+            server.write_u8(2).await.unwrap();
+            server.write_all(&[0, 8, 8, 5, 0, 0, 4, 4]).await.unwrap();
+            // capabilities, encoded in two bytes
+            server.write_all(&[0x81, 0x01]).await.unwrap();
+
+            let mut selected = [0; 5];
+            server.read_exact(&mut selected).await.unwrap();
+            selected
+        });
+
+        let version = Connection::init(&mut client).await.unwrap();
+        assert_eq!(version, Version::V5_4);
+
+        let selected = server.await.unwrap();
+        assert_eq!(selected, [0, 0, 4, 5, 0]);
+    }
+
+    #[tokio::test]
+    async fn should_fall_back_to_classic_handshake() {
+        let (mut client, mut server) = duplex(64);
+
+        let server = tokio::spawn(async move {
+            read_client_handshake(&mut server).await;
+            server.write_all(&[0, 0, 4, 4]).await.unwrap();
+        });
+
+        let version = Connection::init(&mut client).await.unwrap();
+        assert_eq!(version, Version::V4_4);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn should_negotiate_with_a_server_that_only_speaks_4_0() {
+        let (mut client, mut server) = duplex(64);
+
+        let server = tokio::spawn(async move {
+            let mut handshake = [0; 20];
+            server.read_exact(&mut handshake).await.unwrap();
+            // compares every slot as a whole, without knowing about ranges
+            let offered = handshake[4..].chunks(4).any(|slot| slot == [0, 0, 0, 4]);
+            let answer = if offered { [0, 0, 0, 4] } else { [0, 0, 0, 0] };
+            server.write_all(&answer).await.unwrap();
+        });
+
+        let version = Connection::init(&mut client).await.unwrap();
+        assert_eq!(version, Version::V4);
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn should_reject_manifest_without_a_common_version() {
+        let (mut client, mut server) = duplex(64);
+
+        let server = tokio::spawn(async move {
+            read_client_handshake(&mut server).await;
+            server.write_all(&Version::MANIFEST_V1).await.unwrap();
+            server.write_u8(1).await.unwrap();
+            server.write_all(&[0, 1, 1, 6]).await.unwrap();
+            server.write_u8(0).await.unwrap();
+
+            let mut selected = [0xFF; 4];
+            server.read_exact(&mut selected).await.unwrap();
+            selected
+        });
+
+        let error = Connection::init(&mut client).await.unwrap_err();
+        assert!(matches!(error, Error::UnsupportedVersion(6, 1)));
+
+        let selected = server.await.unwrap();
+        assert_eq!(selected, [0, 0, 0, 0]);
+    }
 
     #[test]
     fn should_parse_uri() {
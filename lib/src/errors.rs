@@ -1,6 +1,6 @@
 #[cfg(feature = "unstable-serde-packstream-format")]
 use crate::packstream::{de, ser};
-use crate::DeError;
+use crate::{version::Version, DeError};
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
@@ -33,6 +33,24 @@ pub enum Error {
     #[error("connection error")]
     ConnectionError,
 
+    #[error("The graph has been closed")]
+    GraphClosed,
+
+    #[error("Timed out after {1:?} while {0}")]
+    ConnectionTimeout(&'static str, std::time::Duration),
+
+    #[error("Timed out after {0:?} while waiting for a connection from the pool")]
+    ConnectionAcquisitionTimeout(std::time::Duration),
+
+    #[error("The query has been cancelled")]
+    Cancelled,
+
+    #[error("The query did not complete within {0:?}")]
+    Timeout(std::time::Duration),
+
+    #[error("The transaction has been rolled back because one of its queries was interrupted")]
+    TransactionTerminated,
+
     #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
     #[error("The connection has been closed [{}]: {}", _0.code, _0.message)]
     ConnectionClosed(crate::bolt::Failure),
@@ -61,6 +79,9 @@ pub enum Error {
     #[error("Bolt Version {0}.{1} is not supported")]
     UnsupportedVersion(u8, u8),
 
+    #[error("Impersonation requires Bolt 4.4 or later, but the server uses Bolt {0}")]
+    ImpersonationNotSupported(Version),
+
     #[error(
         "Protocol mismatch: Expected a Bolt version as response, \
              got {0:08x} instead (maybe you connected to the HTTP port?)"
@@ -113,6 +134,29 @@ pub enum Error {
     ServerUnavailableError(String),
 }
 
+impl Error {
+    /// Whether the operation that failed with this error may succeed if it is tried again.
+    pub(crate) fn can_retry(&self) -> bool {
+        match self {
+            Error::Neo4j(e) => e.can_retry(),
+            Error::ConnectionTimeout(..) => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the operation that failed with this error used a database that does not exist,
+    /// e.g. a home database that has been dropped.
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    pub(crate) fn is_fatal_discovery(&self) -> bool {
+        let kind = match self {
+            Error::Neo4j(e) => e.kind(),
+            Error::RoutingTableError((code, _)) => Neo4jErrorKind::new(code),
+            _ => return false,
+        };
+        kind == Neo4jErrorKind::Client(Neo4jClientErrorKind::FatalDiscovery)
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Neo4jErrorKind {
     Client(Neo4jClientErrorKind),
@@ -273,6 +317,7 @@ impl std::convert::From<deadpool::managed::PoolError<Error>> for Error {
     fn from(e: deadpool::managed::PoolError<Error>) -> Self {
         match e {
             deadpool::managed::PoolError::Backend(e) => e,
+            deadpool::managed::PoolError::Closed => Error::GraphClosed,
             _ => Error::ConnectionError,
         }
     }
@@ -1,29 +1,36 @@
 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
 use {
+    crate::bookmarks::SessionBookmarks,
     crate::connection::{ConnectionInfo, Routing},
     crate::graph::ConnectionPoolManager::Routed,
-    crate::routing::{ClusterRoutingTableProvider, RoutedConnectionManager},
+    crate::routing::RoutedConnectionManager,
+    crate::session::{Session, SessionConfig},
     crate::summary::ResultSummary,
     log::debug,
-    std::sync::Arc,
 };
 
+use crate::auth::{AuthToken, AuthTokenManager};
+use crate::bookmarks::BookmarkManager;
+use crate::connection::ServerInfo;
 use crate::graph::ConnectionPoolManager::Direct;
+use crate::metrics::Metrics;
 use crate::pool::ManagedConnection;
 use crate::query::RetryableQuery;
 use crate::retry::Retry;
 use crate::RunResult;
 use crate::{
-    config::{Config, ConfigBuilder, Database, LiveConfig},
+    config::{Config, ConfigBuilder, Database, LiveConfig, TransactionConfig},
     errors::Result,
     pool::{create_pool, ConnectionPool},
     query::Query,
     stream::DetachedRowStream,
     txn::Txn,
+    version::Version,
     Operation,
 };
 use backon::{ExponentialBuilder, RetryableWithContext};
-use std::time::Duration;
+use futures::future::BoxFuture;
+use std::{sync::Arc, time::Duration};
 
 #[derive(Clone)]
 pub(crate) enum ConnectionPoolManager {
@@ -33,12 +40,55 @@ pub(crate) enum ConnectionPoolManager {
 }
 
 impl ConnectionPoolManager {
+    /// Returns a pooled connection to a server of `db` that is authenticated with `auth`,
+    /// or with the token of the configured [`crate::AuthTokenManager`] if no `auth` is given.
     #[allow(unused_variables)]
-    pub(crate) async fn get(&self, operation: Option<Operation>) -> Result<ManagedConnection> {
+    pub(crate) async fn get(
+        &self,
+        db: Option<&Database>,
+        operation: Option<Operation>,
+        auth: Option<&AuthToken>,
+    ) -> Result<ManagedConnection> {
+        let mut connection = match self {
+            #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+            Routed(manager) => manager.get(db, operation).await?,
+            Direct(pool) => crate::pool::acquire(pool).await?,
+        };
+        let token;
+        let auth = match auth {
+            Some(auth) => auth,
+            // a pooled connection keeps the token of the last handle that used it
+            None if connection.version() >= Version::V5_1 => {
+                token = self.auth().get_token().await?;
+                &token
+            }
+            // older connections are discarded by the pool once their token is outdated
+            None => return Ok(connection),
+        };
+        if connection.auth() != Some(auth) {
+            connection.reauth(auth).await?;
+        }
+        Ok(connection)
+    }
+
+    /// The configured [`crate::AuthTokenManager`].
+    pub(crate) fn auth(&self) -> &dyn AuthTokenManager {
+        match self {
+            #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+            Routed(manager) => manager.auth(),
+            Direct(pool) => pool.manager().auth(),
+        }
+    }
+
+    /// Connects to the server, or to a router when routing, to find out who is on the other end.
+    async fn server_info(&self, auth: Option<&AuthToken>) -> Result<ServerInfo> {
         match self {
             #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
-            Routed(manager) => manager.get(operation).await,
-            Direct(pool) => pool.get().await.map_err(crate::Error::from),
+            Routed(manager) => manager.server_info(auth).await,
+            Direct(pool) => {
+                let connection = self.get(None, None, auth).await?;
+                Ok(connection.server_info(pool.manager().address()))
+            }
         }
     }
 
@@ -49,6 +99,32 @@ impl ConnectionPoolManager {
             Direct(pool) => pool.manager().backoff(),
         }
     }
+
+    /// Lets the routing forget what it knows about databases that turned out not to exist.
+    #[allow(unused_variables)]
+    fn handle_error(&self, error: &crate::Error) {
+        #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+        if let Routed(manager) = self {
+            manager.handle_error(error);
+        }
+    }
+
+    fn metrics(&self) -> Metrics {
+        let pools = match self {
+            #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+            Routed(manager) => manager.metrics(),
+            Direct(pool) => vec![crate::pool::metrics(pool)],
+        };
+        Metrics { pools }
+    }
+
+    async fn close(&self, timeout: Option<Duration>) {
+        match self {
+            #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+            Routed(manager) => manager.close(timeout).await,
+            Direct(pool) => crate::pool::close(pool, timeout).await,
+        }
+    }
 }
 
 /// A neo4j database abstraction.
@@ -56,8 +132,9 @@ impl ConnectionPoolManager {
 /// are reference-counted.
 #[derive(Clone)]
 pub struct Graph {
-    config: LiveConfig,
+    pub(crate) config: LiveConfig,
     pool: ConnectionPoolManager,
+    auth: Option<Arc<AuthToken>>,
 }
 
 /// Returns a [`Query`] which provides methods like [`Query::param`] to add parameters to the query
@@ -74,25 +151,24 @@ impl Graph {
         {
             let info = ConnectionInfo::new(
                 &config.uri,
-                &config.user,
-                &config.password,
+                config.auth.clone(),
                 &config.tls_config,
+                &config.connection,
             )?;
             if matches!(info.init.routing, Routing::Yes(_)) {
                 debug!("Routing enabled, creating a routed connection manager");
-                let pool = Routed(RoutedConnectionManager::new(
-                    &config,
-                    Arc::new(ClusterRoutingTableProvider),
-                )?);
+                let pool = Routed(RoutedConnectionManager::new(&config)?);
                 Ok(Graph {
                     config: config.into_live_config(),
                     pool,
+                    auth: None,
                 })
             } else {
                 let pool = Direct(create_pool(&config)?);
                 Ok(Graph {
                     config: config.into_live_config(),
                     pool,
+                    auth: None,
                 })
             }
         }
@@ -102,6 +178,7 @@ impl Graph {
             Ok(Graph {
                 config: config.into_live_config(),
                 pool,
+                auth: None,
             })
         }
     }
@@ -120,14 +197,205 @@ impl Graph {
         Self::connect(config)
     }
 
+    /// Returns a handle to the same database that runs all queries and transactions
+    /// as the user of `auth` instead of the configured one.
+    ///
+    /// The handle shares the connection pool with this graph, so that many users can be served
+    /// without a pool per user. Pooled connections are re-authenticated with `LOGOFF` and `LOGON`
+    /// when they are handed out to another user,
+    /// which requires a server that supports Bolt 5.1 or later.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use neo4rs::{AuthToken, Graph};
+    ///
+    /// # async fn run(graph: Graph) -> neo4rs::Result<()> {
+    /// let alice = graph.with_auth(AuthToken::basic("alice", "secret"));
+    /// alice.run("CREATE (:Note {text: 'written by alice'})").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_auth(&self, auth: AuthToken) -> Self {
+        Self {
+            auth: Some(Arc::new(auth)),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a handle to the same database that runs all queries and transactions
+    /// on behalf of `user`, with the permissions of that user.
+    ///
+    /// The configured user needs the `IMPERSONATE` privilege for `user`,
+    /// and the server must support Bolt 4.4 or later.
+    /// Unless a database is given, queries run on the home database of the impersonated user.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use neo4rs::Graph;
+    ///
+    /// # async fn run(graph: Graph) -> neo4rs::Result<()> {
+    /// let alice = graph.with_imp_user("alice");
+    /// alice.run("CREATE (:Note {text: 'on behalf of alice'})").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_imp_user(&self, user: impl Into<String>) -> Self {
+        let mut graph = self.clone();
+        graph.config.imp_user = Some(Arc::from(user.into()));
+        graph
+    }
+
+    /// Returns a handle to the same database that applies `txn_config`
+    /// to all transactions it starts and to all queries it runs or executes.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use neo4rs::{Graph, TransactionConfig};
+    /// use std::time::Duration;
+    ///
+    /// # async fn run(graph: Graph) -> neo4rs::Result<()> {
+    /// let bounded = graph.with_txn_config(
+    ///     TransactionConfig::new().with_timeout(Duration::from_secs(30)),
+    /// );
+    /// bounded.run("MATCH (n) SET n.visited = true").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_txn_config(&self, txn_config: TransactionConfig) -> Self {
+        let mut graph = self.clone();
+        graph.config.txn_config = txn_config;
+        graph
+    }
+
+    /// Creates a [`Session`] that chains the bookmarks of its transactions,
+    /// so that each of them sees the writes of the previous ones.
+    ///
+    /// The session shares the connection pool with this graph,
+    /// and its committed transactions also update the configured [`BookmarkManager`].
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    pub fn session(&self, config: SessionConfig) -> Session {
+        let mut graph = self.clone();
+        if let Some(db) = config.db {
+            graph.config.db = Some(db);
+        }
+        if let Some(user) = config.imp_user {
+            graph.config.imp_user = Some(Arc::from(user));
+        }
+        Session::new(graph, config.operation, config.bookmarks)
+    }
+
+    /// Returns a snapshot of the connection pools behind this graph,
+    /// which are shared with all of its clones.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use neo4rs::Graph;
+    ///
+    /// # fn run(graph: Graph) {
+    /// for pool in graph.metrics().pools {
+    ///     println!(
+    ///         "{}: {} of {} connections in use, {} acquisitions timed out",
+    ///         pool.address, pool.in_use, pool.max_size, pool.acquisition_timeouts
+    ///     );
+    /// }
+    /// # }
+    /// ```
+    pub fn metrics(&self) -> Metrics {
+        self.pool.metrics()
+    }
+
+    /// Checks that the server can be reached and accepts the credentials of this graph.
+    ///
+    /// Connecting to a graph is lazy, so that a wrong address or password
+    /// would otherwise only surface with the first query.
+    /// When routing, connectivity is verified against a router.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use neo4rs::Graph;
+    ///
+    /// # async fn run() -> neo4rs::Result<()> {
+    /// let graph = Graph::new("bolt://localhost:7687", "neo4j", "neo")?;
+    /// graph.verify_connectivity().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn verify_connectivity(&self) -> Result<()> {
+        self.server_info().await.map(drop)
+    }
+
+    /// Returns the agent, protocol version and address of the server,
+    /// and the id of the connection that was used to ask it.
+    ///
+    /// When routing, the information is about a router.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use neo4rs::Graph;
+    ///
+    /// # async fn run(graph: Graph) -> neo4rs::Result<()> {
+    /// let info = graph.server_info().await?;
+    /// println!(
+    ///     "{} at {} speaks Bolt {}",
+    ///     info.agent, info.address, info.protocol_version
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn server_info(&self) -> Result<ServerInfo> {
+        self.pool.server_info(self.auth.as_deref()).await
+    }
+
+    /// Closes all connections of this graph and stops refreshing the routing table.
+    ///
+    /// Waits for the connections that are still in use, e.g. by open transactions or streams,
+    /// to be returned, and tells the server goodbye on every connection.
+    /// Use [`Graph::close_with_timeout`] to close the graph while holding one of them.
+    /// The graph and all of its clones fail with [`crate::Error::GraphClosed`] afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use neo4rs::{query, Graph};
+    ///
+    /// # async fn run(graph: Graph) -> neo4rs::Result<()> {
+    /// graph.run(query("CREATE (n:Node)")).await?;
+    /// graph.close().await;
+    /// assert!(graph.run(query("RETURN 1")).await.is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn close(&self) {
+        self.pool.close(None).await;
+    }
+
+    /// Closes all connections of this graph like [`Graph::close`],
+    /// but waits at most `timeout` for the connections that are still in use.
+    ///
+    /// Connections that are returned afterwards are closed without telling the server goodbye.
+    /// Unlike `close`, this can be called while holding a transaction or stream of this graph.
+    pub async fn close_with_timeout(&self, timeout: Duration) {
+        self.pool.close(Some(timeout)).await;
+    }
+
     /// Starts a new transaction on the configured database.
     /// All queries that needs to be run/executed within the transaction
     /// should be executed using either [`Txn::run`] or [`Txn::execute`]
     ///
     /// Transactions will not be automatically retried on any failure.
     pub async fn start_txn(&self) -> Result<Txn> {
-        self.impl_start_txn_on(self.config.db.clone(), Operation::Write, &[])
-            .await
+        self.impl_start_txn_on(
+            self.config.db.clone(),
+            Operation::Write,
+            &self.config.bookmark_manager,
+        )
+        .await
     }
 
     /// Starts a new transaction on the configured database specifying the desired operation.
@@ -135,18 +403,23 @@ impl Graph {
     /// should be executed using either [`Txn::run`] or [`Txn::execute`]
     ///
     /// Transactions will not be automatically retried on any failure.
+    ///
+    /// Given bookmarks replace the ones of the configured [`BookmarkManager`],
+    /// and the manager is not updated once the transaction is committed.
     #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
     pub async fn start_txn_as(
         &self,
         operation: Operation,
         bookmarks: Option<Vec<String>>,
     ) -> Result<Txn> {
-        self.impl_start_txn_on(
-            self.config.db.clone(),
-            operation,
-            bookmarks.as_deref().unwrap_or_default(),
-        )
-        .await
+        let bookmarks = match bookmarks {
+            Some(bookmarks) => {
+                Arc::new(SessionBookmarks::new(bookmarks, None)) as Arc<dyn BookmarkManager>
+            }
+            None => Arc::clone(&self.config.bookmark_manager),
+        };
+        self.impl_start_txn_on(self.config.db.clone(), operation, &bookmarks)
+            .await
     }
 
     /// Starts a new transaction on the provided database.
@@ -155,26 +428,75 @@ impl Graph {
     ///
     /// Transactions will not be automatically retried on any failure.
     pub async fn start_txn_on(&self, db: impl Into<Database>) -> Result<Txn> {
-        self.impl_start_txn_on(Some(db.into()), Operation::Write, &[])
-            .await
+        self.impl_start_txn_on(
+            Some(db.into()),
+            Operation::Write,
+            &self.config.bookmark_manager,
+        )
+        .await
     }
 
     #[allow(unused_variables)]
-    async fn impl_start_txn_on(
+    pub(crate) async fn impl_start_txn_on(
         &self,
         db: Option<Database>,
         operation: Operation,
-        bookmarks: &[String],
+        bookmarks: &Arc<dyn BookmarkManager>,
     ) -> Result<Txn> {
-        let connection = self.pool.get(Some(operation)).await?;
+        let db = self.resolve_db(db).await?;
+        let imp_user = self.config.imp_user.as_deref();
         #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
         {
-            Txn::new(db, self.config.fetch_size, connection, operation, bookmarks).await
+            let previous = bookmarks.get_bookmarks(db.as_deref()).await?;
+            let connection = self
+                .pool
+                .get(db.as_ref(), Some(operation), self.auth.as_deref())
+                .await
+                .map_err(|e| self.handled(e))?;
+            let mut txn = Txn::new(
+                db.clone(),
+                self.config.fetch_size,
+                connection,
+                operation,
+                &previous,
+                imp_user,
+                &self.config.txn_config,
+            )
+            .await
+            .map_err(|e| self.handled(e))?;
+            txn.track_bookmarks(Arc::clone(bookmarks), db, previous);
+            Ok(txn)
         }
         #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
         {
-            Txn::new(db, self.config.fetch_size, connection, operation).await
+            let connection = self
+                .pool
+                .get(db.as_ref(), Some(operation), self.auth.as_deref())
+                .await?;
+            Txn::new(
+                db,
+                self.config.fetch_size,
+                connection,
+                operation,
+                imp_user,
+                &self.config.txn_config,
+            )
+            .await
+        }
+    }
+
+    /// The database to use, which is the home database of the user, or of the impersonated user,
+    /// if no database has been given.
+    /// With routing, the home database is needed to find its servers, and is cached until
+    /// its routing table expires. Without routing, the server resolves the home database by itself.
+    async fn resolve_db(&self, db: Option<Database>) -> Result<Option<Database>> {
+        #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+        if let (None, Routed(routed)) = (&db, &self.pool) {
+            return routed
+                .home_db(self.auth.as_ref(), self.config.imp_user.as_deref())
+                .await;
         }
+        Ok(db)
     }
 
     /// Runs a query on the configured database using a connection from the connection pool,
@@ -189,8 +511,13 @@ impl Graph {
     ///
     /// use [`Graph::execute`] when you are interested in the result stream
     pub async fn run(&self, q: impl Into<Query>) -> Result<RunResult> {
-        self.impl_run_on(self.config.db.clone(), q.into(), Operation::Write)
-            .await
+        self.impl_run_on(
+            self.config.db.clone(),
+            q.into(),
+            Operation::Write,
+            &self.config.bookmark_manager,
+        )
+        .await
     }
 
     /// Runs a query on the provided database using a connection from the connection pool.
@@ -211,25 +538,51 @@ impl Graph {
         q: impl Into<Query>,
         operation: Operation,
     ) -> Result<ResultSummary> {
-        self.impl_run_on(Some(db.into()), q.into(), operation).await
+        self.impl_run_on(
+            Some(db.into()),
+            q.into(),
+            operation,
+            &self.config.bookmark_manager,
+        )
+        .await
     }
 
     #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
     pub async fn run_on(&self, db: impl Into<Database>, q: impl Into<Query>) -> Result<()> {
-        self.impl_run_on(Some(db.into()), q.into(), Operation::Write)
-            .await
+        self.impl_run_on(
+            Some(db.into()),
+            q.into(),
+            Operation::Write,
+            &self.config.bookmark_manager,
+        )
+        .await
     }
 
     #[allow(unused_variables)]
-    async fn impl_run_on(
+    pub(crate) async fn impl_run_on(
         &self,
         db: Option<Database>,
         query: Query,
         operation: Operation,
+        bookmarks: &Arc<dyn BookmarkManager>,
     ) -> Result<RunResult> {
-        let query = query.into_retryable(db, operation, &self.pool, None);
+        let db = self.resolve_db(db).await?;
+        #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+        let previous = bookmarks.get_bookmarks(db.as_deref()).await?;
+        #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+        let query = with_bookmarks(query, &previous);
+        let query = query
+            .with_txn_config(&self.config.txn_config)
+            .into_retryable(
+                db.clone(),
+                self.config.imp_user.as_deref(),
+                operation,
+                &self.pool,
+                self.auth.as_deref(),
+                None,
+            );
 
-        let (query, result) = RetryableQuery::retry_run
+        let (_, result) = RetryableQuery::retry_run
             .retry(self.pool.backoff())
             .sleep(tokio::time::sleep)
             .context(query)
@@ -240,27 +593,14 @@ impl Graph {
         match result {
             Ok(result) => {
                 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
-                {
-                    if let Some(bookmark) = result.bookmark.as_deref() {
-                        match &self.pool {
-                            Routed(routed) => {
-                                routed.add_bookmark(bookmark).await;
-                            }
-                            Direct(_) => {}
-                        }
-                    } else if query.is_read() {
-                        match &self.pool {
-                            Routed(routed) => {
-                                debug!("No bookmark received after a read operation, discarding all bookmarks");
-                                routed.clear_bookmarks().await;
-                            }
-                            Direct(_) => {}
-                        }
-                    }
+                if let Some(bookmark) = result.bookmark.as_deref() {
+                    bookmarks
+                        .update_bookmarks(db.as_deref(), &previous, bookmark)
+                        .await;
                 }
                 Ok(result)
             }
-            Err(e) => Err(e.into_inner()),
+            Err(e) => Err(self.handled(e.into_inner())),
         }
     }
 
@@ -281,6 +621,8 @@ impl Graph {
     /// All errors with the `Transient` error class as well as a few other error classes are considered retryable.
     /// This includes errors during a leader election or when the transaction resources on the server (memory, handles, ...) are exhausted.
     /// Retries happen with an exponential backoff until a retry delay exceeds 60s, at which point the query fails with the last error as it would without any retry.
+    ///
+    /// Use [`Graph::read_transaction`] to run several queries in one read transaction.
     pub async fn execute_read(&self, q: impl Into<Query>) -> Result<DetachedRowStream> {
         self.impl_execute_on(self.config.db.clone(), q.into(), Operation::Read)
             .await
@@ -325,7 +667,27 @@ impl Graph {
         query: Query,
         operation: Operation,
     ) -> Result<DetachedRowStream> {
-        let query = query.into_retryable(db, operation, &self.pool, Some(self.config.fetch_size));
+        let db = self.resolve_db(db).await?;
+        // the bookmark of a stream is only known once it is consumed, so it is not tracked
+        #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+        let query = with_bookmarks(
+            query,
+            &self
+                .config
+                .bookmark_manager
+                .get_bookmarks(db.as_deref())
+                .await?,
+        );
+        let query = query
+            .with_txn_config(&self.config.txn_config)
+            .into_retryable(
+                db,
+                self.config.imp_user.as_deref(),
+                operation,
+                &self.pool,
+                self.auth.as_deref(),
+                Some(self.config.fetch_size),
+            );
 
         let (query, result) = RetryableQuery::retry_execute
             .retry(self.pool.backoff())
@@ -335,9 +697,118 @@ impl Graph {
             .notify(Self::log_retry)
             .await;
 
+        result.map_err(|e| self.handled(e.into_inner()))
+    }
+
+    /// Runs `work` in a write transaction on the configured database,
+    /// which is committed if `work` succeeds and rolled back otherwise.
+    ///
+    /// If `work` or the commit fails with a retryable error, the whole unit of work is run again
+    /// in a new transaction, so `work` may be called more than once and should have no side
+    /// effects other than the queries it runs.
+    /// All errors with the `Transient` error class as well as a few other error classes are considered retryable.
+    /// Retries happen with an exponential backoff until a retry delay exceeds 60s, at which point the transaction fails with the last error as it would without any retry.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use neo4rs::{query, Graph};
+    ///
+    /// # async fn run(graph: Graph) -> neo4rs::Result<()> {
+    /// let count = graph
+    ///     .write_transaction(|txn| {
+    ///         Box::pin(async move {
+    ///             txn.run(query("CREATE (:Person {name: $name})").param("name", "alice"))
+    ///                 .await?;
+    ///             let mut rows = txn.execute("MATCH (p:Person) RETURN count(p) AS count").await?;
+    ///             let row = rows.next(txn.handle()).await?.expect("one row");
+    ///             Ok(row.get::<i64>("count")?)
+    ///         })
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_transaction<F, R>(&self, work: F) -> Result<R>
+    where
+        F: FnMut(&mut Txn) -> BoxFuture<'_, Result<R>>,
+    {
+        self.impl_execute_txn(Operation::Write, &self.config.bookmark_manager, work)
+            .await
+    }
+
+    /// Runs `work` in a read transaction on the configured database,
+    /// which is committed if `work` succeeds and rolled back otherwise.
+    ///
+    /// Retries `work` in the same way as [`Graph::write_transaction`].
+    pub async fn read_transaction<F, R>(&self, work: F) -> Result<R>
+    where
+        F: FnMut(&mut Txn) -> BoxFuture<'_, Result<R>>,
+    {
+        self.impl_execute_txn(Operation::Read, &self.config.bookmark_manager, work)
+            .await
+    }
+
+    pub(crate) async fn impl_execute_txn<F, R>(
+        &self,
+        operation: Operation,
+        bookmarks: &Arc<dyn BookmarkManager>,
+        work: F,
+    ) -> Result<R>
+    where
+        F: FnMut(&mut Txn) -> BoxFuture<'_, Result<R>>,
+    {
+        let attempt = |mut work: F| async move {
+            let result = self.execute_txn_once(operation, bookmarks, &mut work).await;
+            (work, result)
+        };
+
+        let (_, result) = attempt
+            .retry(self.pool.backoff())
+            .sleep(tokio::time::sleep)
+            .context(work)
+            .when(|e| matches!(e, Retry::Yes(_)))
+            .notify(Self::log_retry)
+            .await;
+
         result.map_err(Retry::into_inner)
     }
 
+    async fn execute_txn_once<F, R>(
+        &self,
+        operation: Operation,
+        bookmarks: &Arc<dyn BookmarkManager>,
+        work: &mut F,
+    ) -> Result<R, Retry<crate::Error>>
+    where
+        F: FnMut(&mut Txn) -> BoxFuture<'_, Result<R>>,
+    {
+        let mut txn = self
+            .impl_start_txn_on(self.config.db.clone(), operation, bookmarks)
+            .await
+            .map_err(retryable)?;
+
+        let result = match work(&mut txn).await {
+            Ok(result) => result,
+            Err(e) => {
+                if let Err(rollback) = txn.rollback().await {
+                    log::debug!("Failed to roll back the transaction: {rollback}");
+                }
+                return Err(retryable(e));
+            }
+        };
+
+        txn.commit().await.map_err(retryable)?;
+
+        Ok(result)
+    }
+
+    /// Reports a failed operation to the connection pool before it is returned to the caller.
+    fn handled(&self, error: crate::Error) -> crate::Error {
+        self.pool.handle_error(&error);
+        error
+    }
+
     fn log_retry(e: &Retry<crate::Error>, delay: Duration) {
         let level = match delay.as_millis() {
             0..=499 => log::Level::Debug,
@@ -348,7 +819,790 @@ impl Graph {
     }
 }
 
+/// Makes the query wait for `bookmarks` before it runs.
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+fn with_bookmarks(query: Query, bookmarks: &[String]) -> Query {
+    if bookmarks.is_empty() {
+        query
+    } else {
+        query.extra("bookmarks", bookmarks.to_vec())
+    }
+}
+
+/// Errors with a retryable Neo4j error code and connection timeouts are retried,
+/// all others are permanent.
+fn retryable(error: crate::Error) -> Retry<crate::Error> {
+    if error.can_retry() {
+        Retry::yes(error)
+    } else {
+        Retry::no(error)
+    }
+}
+
 const _: () = {
     const fn assert_send_sync<T: ?Sized + Send + Sync>() {}
     assert_send_sync::<Graph>();
 };
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_server::{
+            self, TestServer, BEGIN, COMMIT, GOODBYE, HELLO, LOGOFF, LOGON, RESET, ROLLBACK, RUN,
+        },
+        types::{BoltMap, BoltType},
+        version::Version,
+        CancellationToken, Error,
+    };
+    use std::{
+        sync::atomic::{AtomicBool, Ordering},
+        time::Duration,
+    };
+
+    async fn connect(server: &TestServer) -> Graph {
+        let config = ConfigBuilder::default()
+            .uri(server.uri())
+            .user("neo4j")
+            .password("neo4j")
+            .max_connections(1)
+            .build()
+            .unwrap();
+        Graph::connect(config).unwrap()
+    }
+
+    #[tokio::test]
+    async fn with_auth_reauthenticates_pooled_connection() {
+        let server = TestServer::start(Version::V5_1, |_| None).await;
+        let graph = connect(&server).await;
+
+        let alice = graph.with_auth(AuthToken::basic("alice", "secret"));
+        alice.run("RETURN 1").await.unwrap();
+        alice.run("RETURN 1").await.unwrap();
+        graph.run("RETURN 1").await.unwrap();
+
+        let requests = server.requests();
+        assert!(requests.iter().all(|request| request.connection == 0));
+
+        let messages = requests
+            .iter()
+            .filter_map(|request| match request.signature {
+                HELLO => Some("HELLO".to_owned()),
+                LOGON => {
+                    let principal = request.map(0).get::<String>("principal").unwrap();
+                    Some(format!("LOGON {principal}"))
+                }
+                LOGOFF => Some("LOGOFF".to_owned()),
+                RESET => Some("RESET".to_owned()),
+                RUN => Some("RUN".to_owned()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [
+                "HELLO",
+                "LOGON neo4j",
+                "LOGOFF",
+                "LOGON alice",
+                "RUN",
+                // the connection keeps the token of alice while she uses it
+                "RESET",
+                "RUN",
+                "RESET",
+                "LOGOFF",
+                "LOGON neo4j",
+                "RUN",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn failed_reauthentication_discards_the_connection() {
+        let server = TestServer::start(Version::V5_1, |request| {
+            let rejected = request.signature == LOGON
+                && request
+                    .map(0)
+                    .get::<String>("principal")
+                    .is_ok_and(|principal| principal == "mallory");
+            rejected.then(|| {
+                vec![test_server::failure(
+                    "Neo.ClientError.Security.Unauthorized",
+                    "no",
+                )]
+            })
+        })
+        .await;
+        let graph = connect(&server).await;
+
+        let error = graph
+            .with_auth(AuthToken::basic("mallory", "secret"))
+            .run("RETURN 1")
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error, crate::Error::Neo4j(ref e) if e.code() == "Neo.ClientError.Security.Unauthorized"),
+            "{error}"
+        );
+
+        // the logged off connection is replaced
+        graph.run("RETURN 1").await.unwrap();
+        let runs = server
+            .requests()
+            .into_iter()
+            .filter(|request| request.signature == RUN)
+            .map(|request| request.connection)
+            .collect::<Vec<_>>();
+        assert_eq!(runs, [1]);
+        assert_eq!(graph.metrics().pools[0].closed, 1);
+    }
+
+    #[tokio::test]
+    async fn with_auth_requires_bolt_5_1() {
+        let server = TestServer::start(Version::V5_0, |_| None).await;
+        let graph = connect(&server).await;
+
+        let error = graph
+            .with_auth(AuthToken::basic("alice", "secret"))
+            .run("RETURN 1")
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error, crate::Error::AuthenticationError(ref msg) if msg.contains("5.1")),
+            "{error}"
+        );
+    }
+
+    #[tokio::test]
+    async fn with_imp_user_sends_imp_user() {
+        let server = TestServer::start(Version::V4_4, |_| None).await;
+        let graph = connect(&server).await.with_imp_user("alice");
+
+        graph.run("RETURN 1").await.unwrap();
+        graph.start_txn().await.unwrap();
+
+        let imp_users = server
+            .requests()
+            .into_iter()
+            .filter(|request| matches!(request.signature, RUN | BEGIN))
+            .map(|request| {
+                let extra = request.map(request.fields.len() - 1);
+                (request.signature, extra.get::<String>("imp_user").unwrap())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            imp_users,
+            [(RUN, "alice".to_owned()), (BEGIN, "alice".to_owned())]
+        );
+    }
+
+    #[tokio::test]
+    async fn with_imp_user_requires_bolt_4_4() {
+        let server = TestServer::start(Version::V4_3, |_| None).await;
+        let graph = connect(&server).await.with_imp_user("alice");
+
+        let error = graph.run("RETURN 1").await.unwrap_err();
+        assert!(
+            matches!(
+                error,
+                crate::Error::ImpersonationNotSupported(Version::V4_3)
+            ),
+            "{error}"
+        );
+        let error = graph.start_txn().await.err().unwrap();
+        assert!(
+            matches!(
+                error,
+                crate::Error::ImpersonationNotSupported(Version::V4_3)
+            ),
+            "{error}"
+        );
+        assert!(server
+            .requests()
+            .iter()
+            .all(|request| request.signature != RUN));
+    }
+
+    #[tokio::test]
+    async fn with_txn_config_sends_timeout_and_metadata() {
+        let server = TestServer::start(Version::V4_4, |_| None).await;
+        let metadata = [("app".into(), "reporting".into())].into_iter().collect();
+        let graph = connect(&server).await.with_txn_config(
+            TransactionConfig::new()
+                .with_timeout(Duration::from_secs(30))
+                .with_metadata(metadata),
+        );
+
+        graph.run("RETURN 1").await.unwrap();
+        graph.start_txn().await.unwrap();
+
+        let requests = server
+            .requests()
+            .into_iter()
+            .filter(|request| matches!(request.signature, RUN | BEGIN))
+            .collect::<Vec<_>>();
+        assert_eq!(requests.len(), 2);
+        for request in requests {
+            let extra = request.map(request.fields.len() - 1);
+            assert_eq!(extra.get::<i64>("tx_timeout").unwrap(), 30_000);
+            let metadata = extra.get::<BoltMap>("tx_metadata").unwrap();
+            assert_eq!(metadata.get::<String>("app").unwrap(), "reporting");
+        }
+    }
+
+    #[tokio::test]
+    async fn metrics_are_shared_with_clones() {
+        let server = TestServer::start(Version::V4_4, |_| None).await;
+        let graph = connect(&server).await;
+
+        let txn = graph.clone().start_txn().await.unwrap();
+        let metrics = graph.metrics();
+        assert_eq!(metrics.pools.len(), 1);
+        assert_eq!(metrics.pools[0].address, server.uri());
+        assert_eq!((metrics.in_use(), metrics.idle()), (1, 0));
+
+        drop(txn);
+        graph.run("RETURN 1").await.unwrap();
+        let pool = graph.metrics().pools.remove(0);
+        assert_eq!((pool.in_use, pool.idle), (0, 1));
+        assert_eq!((pool.created, pool.closed), (1, 0));
+        assert_eq!(pool.acquisition_wait.count, 2);
+    }
+
+    #[tokio::test]
+    async fn server_info_describes_the_server() {
+        let server = TestServer::start(Version::V4_4, |_| None).await;
+        let graph = connect(&server).await;
+
+        graph.verify_connectivity().await.unwrap();
+        let info = graph.server_info().await.unwrap();
+        assert_eq!(
+            info,
+            ServerInfo {
+                address: server.uri(),
+                agent: "Neo4j/5.0.0".to_owned(),
+                protocol_version: Version::V4_4,
+                connection_id: "bolt-0".to_owned(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn verify_connectivity_fails_with_wrong_credentials() {
+        let server = TestServer::start(Version::V4_4, |request| {
+            (request.signature == HELLO).then(|| {
+                vec![test_server::failure(
+                    "Neo.ClientError.Security.Unauthorized",
+                    "wrong password",
+                )]
+            })
+        })
+        .await;
+        let graph = connect(&server).await;
+
+        assert!(matches!(
+            graph.verify_connectivity().await,
+            Err(Error::Neo4j(e)) if e.message() == "wrong password"
+        ));
+    }
+
+    #[tokio::test]
+    async fn replaces_connection_that_exceeds_the_recv_timeout_hint() {
+        let server = TestServer::start(Version::V4_4, |request| match request.signature {
+            HELLO => {
+                let hints = [("connection.recv_timeout_seconds".into(), 1.into())];
+                Some(vec![test_server::success([
+                    ("server", "Neo4j/5.0.0".into()),
+                    (
+                        "connection_id",
+                        format!("bolt-{}", request.connection).into(),
+                    ),
+                    ("hints", BoltType::Map(hints.into_iter().collect())),
+                ])])
+            }
+            // the first connection is half-open and never answers
+            RUN if request.connection == 0 => Some(vec![]),
+            _ => None,
+        })
+        .await;
+        let graph = connect(&server).await;
+
+        graph.run("RETURN 1").await.unwrap();
+
+        let runs = server
+            .requests()
+            .into_iter()
+            .filter(|request| request.signature == RUN)
+            .map(|request| request.connection)
+            .collect::<Vec<_>>();
+        assert_eq!(runs, [0, 1]);
+        let pool = graph.metrics().pools.remove(0);
+        assert_eq!((pool.created, pool.closed), (2, 1));
+    }
+
+    /// Never answers a query `slow`, until the client interrupts it with a `RESET`.
+    async fn slow_server() -> TestServer {
+        let running = AtomicBool::new(false);
+        TestServer::start(Version::V4_4, move |request| match request.signature {
+            RUN if request.fields[0] == BoltType::from("slow") => {
+                running.store(true, Ordering::SeqCst);
+                Some(vec![])
+            }
+            RESET if running.swap(false, Ordering::SeqCst) => Some(vec![
+                test_server::failure("Neo.TransientError.Transaction.Terminated", "interrupted"),
+                test_server::success([]),
+            ]),
+            _ => None,
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn query_times_out_and_recovers_the_connection() {
+        let server = slow_server().await;
+        let graph = connect(&server).await;
+
+        let timeout = Duration::from_millis(50);
+        let error = graph
+            .run(query("slow").with_timeout(timeout))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error, Error::Timeout(t) if t == timeout),
+            "{error}"
+        );
+        assert!(server.requests().iter().any(|r| r.signature == RESET));
+
+        graph.run("RETURN 1").await.unwrap();
+        assert!(server.requests().iter().all(|r| r.connection == 0));
+        let pool = graph.metrics().pools.remove(0);
+        assert_eq!((pool.created, pool.closed), (1, 0));
+    }
+
+    #[tokio::test]
+    async fn query_is_cancelled_with_its_token() {
+        let server = slow_server().await;
+        let graph = connect(&server).await;
+
+        let token = CancellationToken::new();
+        tokio::spawn({
+            let token = token.clone();
+            async move {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                token.cancel();
+            }
+        });
+        let error = graph
+            .run(query("slow").with_cancellation(token.clone()))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::Cancelled), "{error}");
+
+        // waiting for a connection is cancelled as well
+        let txn = graph.start_txn().await.unwrap();
+        let error = graph
+            .run(query("RETURN 1").with_cancellation(token))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::Cancelled), "{error}");
+        txn.commit().await.unwrap();
+
+        graph.run("RETURN 1").await.unwrap();
+        assert!(server.requests().iter().all(|r| r.connection == 0));
+    }
+
+    #[tokio::test]
+    async fn interrupted_query_terminates_its_transaction() {
+        let server = slow_server().await;
+        let graph = connect(&server).await;
+
+        let mut txn = graph.start_txn().await.unwrap();
+        let timeout = Duration::from_millis(50);
+        let error = txn
+            .run(query("slow").with_timeout(timeout))
+            .await
+            .unwrap_err();
+        assert!(matches!(error, Error::Timeout(_)), "{error}");
+
+        // the RESET rolled back the transaction on the server
+        let error = txn.run("RETURN 1").await.unwrap_err();
+        assert!(matches!(error, Error::TransactionTerminated), "{error}");
+        let error = txn.commit().await.unwrap_err();
+        assert!(matches!(error, Error::TransactionTerminated), "{error}");
+        assert!(server.requests().iter().all(|r| r.signature != COMMIT));
+
+        graph.run("RETURN 1").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn dropping_a_running_query_keeps_the_connection_usable() {
+        let server = slow_server().await;
+        let graph = connect(&server).await;
+
+        let slow = tokio::time::timeout(Duration::from_millis(50), graph.run("slow")).await;
+        assert!(slow.is_err());
+
+        // the pool resets the connection and skips the response of the abandoned query
+        graph.run("RETURN 1").await.unwrap();
+        assert!(server.requests().iter().all(|r| r.connection == 0));
+    }
+
+    #[tokio::test]
+    async fn close_says_goodbye_and_fails_later_calls() {
+        let server = TestServer::start(Version::V4_4, |_| None).await;
+        let graph = connect(&server).await;
+        let clone = graph.clone();
+
+        graph.run("RETURN 1").await.unwrap();
+        graph.close().await;
+
+        server.wait_for(GOODBYE).await;
+        let requests = server.requests();
+        assert_eq!(requests.last().unwrap().signature, GOODBYE);
+        assert_eq!(graph.metrics().pools[0].closed, 1);
+
+        assert!(matches!(
+            graph.run("RETURN 1").await,
+            Err(Error::GraphClosed)
+        ));
+        assert!(matches!(clone.start_txn().await, Err(Error::GraphClosed)));
+        // closing again is fine
+        clone.close().await;
+    }
+
+    #[tokio::test]
+    async fn close_waits_for_connections_in_use() {
+        let server = TestServer::start(Version::V4_4, |_| None).await;
+        let graph = connect(&server).await;
+
+        let txn = graph.start_txn().await.unwrap();
+        let close = tokio::spawn({
+            let graph = graph.clone();
+            async move { graph.close().await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!close.is_finished());
+
+        txn.commit().await.unwrap();
+        close.await.unwrap();
+        server.wait_for(GOODBYE).await;
+        let signatures = server
+            .requests()
+            .into_iter()
+            .map(|request| request.signature)
+            .collect::<Vec<_>>();
+        assert_eq!(signatures[signatures.len() - 2..], [COMMIT, GOODBYE]);
+    }
+
+    #[tokio::test]
+    async fn close_with_timeout_discards_connections_in_use() {
+        let server = TestServer::start(Version::V4_4, |_| None).await;
+        let graph = connect(&server).await;
+
+        let txn = graph.start_txn().await.unwrap();
+        graph.close_with_timeout(Duration::from_millis(50)).await;
+        assert!(matches!(
+            graph.run("RETURN 1").await,
+            Err(Error::GraphClosed)
+        ));
+
+        txn.commit().await.unwrap();
+        assert_eq!(graph.metrics().pools[0].closed, 1);
+        assert!(server.requests().iter().all(|r| r.signature != GOODBYE));
+    }
+
+    fn transaction_messages(server: &TestServer) -> Vec<u8> {
+        server
+            .requests()
+            .into_iter()
+            .map(|request| request.signature)
+            .filter(|signature| matches!(*signature, BEGIN | RUN | COMMIT | ROLLBACK))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn write_transaction_retries_transient_errors() {
+        let failed = AtomicBool::new(false);
+        let server = TestServer::start(Version::V4_4, move |request| {
+            (request.signature == RUN && !failed.swap(true, Ordering::SeqCst)).then(|| {
+                vec![test_server::failure(
+                    "Neo.TransientError.Transaction.DeadlockDetected",
+                    "deadlock",
+                )]
+            })
+        })
+        .await;
+        let graph = connect(&server).await;
+
+        let mut attempts = 0;
+        let result = graph
+            .write_transaction(|txn| {
+                attempts += 1;
+                Box::pin(async move {
+                    txn.run("CREATE (n)").await?;
+                    Ok(42)
+                })
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result, 42);
+        assert_eq!(attempts, 2);
+        assert_eq!(
+            transaction_messages(&server),
+            [BEGIN, RUN, ROLLBACK, BEGIN, RUN, COMMIT]
+        );
+    }
+
+    #[tokio::test]
+    async fn read_transaction_rolls_back_on_permanent_errors() {
+        let server = TestServer::start(Version::V4_4, |request| {
+            (request.signature == RUN).then(|| {
+                vec![test_server::failure(
+                    "Neo.ClientError.Statement.SyntaxError",
+                    "invalid",
+                )]
+            })
+        })
+        .await;
+        let graph = connect(&server).await;
+
+        let mut attempts = 0;
+        let error = graph
+            .read_transaction(|txn| {
+                attempts += 1;
+                Box::pin(async move { txn.run("RETURN").await })
+            })
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, crate::Error::Neo4j(_)), "{error}");
+        assert_eq!(attempts, 1);
+        assert_eq!(transaction_messages(&server), [BEGIN, RUN, ROLLBACK]);
+        let requests = server.requests();
+        let begin = requests.iter().find(|r| r.signature == BEGIN).unwrap();
+        assert_eq!(begin.map(0).get::<String>("mode").unwrap(), "r");
+    }
+
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    #[tokio::test]
+    async fn routes_queries_to_the_cached_home_db() {
+        use crate::test_server::ROUTE;
+
+        let addr = Arc::new(std::sync::OnceLock::<String>::new());
+        let server = TestServer::start(Version::V4_4, {
+            let addr = Arc::clone(&addr);
+            move |request| match request.signature {
+                ROUTE => Some(vec![test_server::routing_table(
+                    addr.get().unwrap(),
+                    "movies",
+                )]),
+                RUN if request.fields[0] == BoltType::from("fail") => {
+                    Some(vec![test_server::failure(
+                        "Neo.ClientError.Database.DatabaseNotFound",
+                        "gone",
+                    )])
+                }
+                _ => None,
+            }
+        })
+        .await;
+        addr.set(server.addr.to_string()).unwrap();
+        let config = ConfigBuilder::default()
+            .uri(format!("neo4j://{}", server.addr))
+            .user("neo4j")
+            .password("neo4j")
+            .build()
+            .unwrap();
+        let graph = Graph::connect(config).unwrap();
+
+        graph.run("RETURN 1").await.unwrap();
+        graph.run("RETURN 1").await.unwrap();
+        let routes = |server: &TestServer| {
+            server
+                .requests()
+                .iter()
+                .filter(|request| request.signature == ROUTE)
+                .count()
+        };
+        assert_eq!(routes(&server), 1);
+
+        // the home database may have been dropped, so it is resolved again
+        assert!(graph.run("fail").await.is_err());
+        graph.run("RETURN 1").await.unwrap();
+        assert_eq!(routes(&server), 2);
+
+        let dbs = server
+            .requests()
+            .into_iter()
+            .filter(|request| request.signature == RUN)
+            .map(|run| run.map(2).get::<String>("db").unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(dbs, ["movies"; 4]);
+    }
+
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    #[tokio::test]
+    async fn resolves_the_seed_router_and_tries_every_resolved_router() {
+        use crate::test_server::ROUTE;
+
+        let unavailable = TestServer::start(Version::V4_4, |request| {
+            (request.signature == ROUTE).then(|| {
+                vec![test_server::failure(
+                    "Neo.TransientError.General.DatabaseUnavailable",
+                    "starting up",
+                )]
+            })
+        })
+        .await;
+        let addr = Arc::new(std::sync::OnceLock::<String>::new());
+        let server = TestServer::start(Version::V4_4, {
+            let addr = Arc::clone(&addr);
+            move |request| {
+                (request.signature == ROUTE)
+                    .then(|| vec![test_server::routing_table(addr.get().unwrap(), "neo4j")])
+            }
+        })
+        .await;
+        addr.set(server.addr.to_string()).unwrap();
+
+        let resolved = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let resolver = crate::ServerAddressResolverFn::new({
+            let resolved = Arc::clone(&resolved);
+            let routers = [unavailable.addr, server.addr].map(|addr| addr.to_string());
+            move |address| {
+                resolved.lock().unwrap().push(address.to_owned());
+                routers.to_vec()
+            }
+        });
+        let config = ConfigBuilder::default()
+            .uri("neo4j://cluster.invalid")
+            .user("neo4j")
+            .password("neo4j")
+            .address_resolver(resolver)
+            .build()
+            .unwrap();
+        let graph = Graph::connect(config).unwrap();
+
+        graph.run("RETURN 1").await.unwrap();
+        assert_eq!(*resolved.lock().unwrap(), ["cluster.invalid:7687"]);
+        let signatures = |server: &TestServer| {
+            server
+                .requests()
+                .iter()
+                .map(|request| request.signature)
+                .filter(|signature| [ROUTE, RUN].contains(signature))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(signatures(&unavailable), [ROUTE]);
+        assert_eq!(signatures(&server), [ROUTE, RUN]);
+    }
+
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    #[tokio::test]
+    async fn routes_with_a_static_routing_table_to_the_least_busy_server() {
+        use crate::routing::{RoutingTable, RoutingTableProvider, Server};
+        use std::{future::Future, pin::Pin};
+
+        #[derive(Debug)]
+        struct StaticProvider(Vec<String>);
+
+        impl RoutingTableProvider for StaticProvider {
+            fn fetch_routing_table(
+                &self,
+                _config: &Config,
+                _bookmarks: &[String],
+                _imp_user: Option<&str>,
+            ) -> Pin<Box<dyn Future<Output = Result<RoutingTable>> + Send>> {
+                let servers = ["READ", "WRITE"].map(|role| Server::new(role, self.0.clone()));
+                let routing_table = RoutingTable::new(Duration::from_secs(300), servers);
+                Box::pin(async move { Ok(routing_table) })
+            }
+        }
+
+        let first = TestServer::start(Version::V4_4, |_| None).await;
+        let second = TestServer::start(Version::V4_4, |_| None).await;
+        let addresses = [&first, &second].map(|server| server.addr.to_string());
+        let config = ConfigBuilder::default()
+            .uri("neo4j://cluster.invalid:7687")
+            .user("neo4j")
+            .password("neo4j")
+            .routing_table_provider(StaticProvider(addresses.to_vec()))
+            .load_balancing_strategy(crate::LeastInFlightStrategy::default())
+            .build()
+            .unwrap();
+        let graph = Graph::connect(config).unwrap();
+
+        let begins = |server: &TestServer| {
+            server
+                .requests()
+                .iter()
+                .filter(|request| request.signature == BEGIN)
+                .count()
+        };
+        let txn = graph.start_txn().await.unwrap();
+        let (busy, idle) = if begins(&first) == 1 {
+            (&first, &second)
+        } else {
+            (&second, &first)
+        };
+
+        // all other transactions avoid the server that is busy with the first one
+        for _ in 0..2 {
+            graph.start_txn().await.unwrap().commit().await.unwrap();
+        }
+        txn.commit().await.unwrap();
+        assert_eq!((begins(busy), begins(idle)), (1, 2));
+    }
+
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    #[tokio::test]
+    async fn bookmark_manager_is_shared_across_clones() {
+        let commits = std::sync::atomic::AtomicUsize::new(0);
+        let server = TestServer::start(Version::V4_4, move |request| {
+            (request.signature == COMMIT).then(|| {
+                let bookmark = format!("bm-{}", commits.fetch_add(1, Ordering::SeqCst) + 1);
+                vec![test_server::success([("bookmark", bookmark.into())])]
+            })
+        })
+        .await;
+        let config = ConfigBuilder::default()
+            .uri(server.uri())
+            .user("neo4j")
+            .password("neo4j")
+            .bookmark_manager(crate::InMemoryBookmarkManager::new().with_bookmarks(None, ["bm-0"]))
+            .build()
+            .unwrap();
+        let graph = Graph::connect(config).unwrap();
+
+        graph
+            .write_transaction(|_| Box::pin(async { Ok(()) }))
+            .await
+            .unwrap();
+        let clone = graph.with_txn_config(TransactionConfig::new());
+        clone.start_txn().await.unwrap().commit().await.unwrap();
+        graph
+            .clone()
+            .start_txn_on("movies")
+            .await
+            .unwrap()
+            .commit()
+            .await
+            .unwrap();
+
+        let bookmarks = server
+            .requests()
+            .into_iter()
+            .filter(|request| request.signature == BEGIN)
+            .map(|begin| begin.map(0).get::<Vec<String>>("bookmarks").unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(bookmarks, [vec!["bm-0"], vec!["bm-1"], vec![]]);
+
+        let manager = &graph.config.bookmark_manager;
+        assert_eq!(manager.get_bookmarks(None).await.unwrap(), ["bm-2"]);
+        assert_eq!(
+            manager.get_bookmarks(Some("movies")).await.unwrap(),
+            ["bm-3"]
+        );
+    }
+}
@@ -493,6 +493,7 @@ mod convert;
 mod errors;
 mod graph;
 mod messages;
+mod metrics;
 #[cfg(feature = "unstable-serde-packstream-format")]
 mod packstream;
 mod pool;
@@ -501,26 +502,42 @@ mod retry;
 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
 mod routing;
 mod row;
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+mod session;
 mod stream;
 #[cfg(feature = "unstable-result-summary")]
 pub mod summary;
+#[cfg(test)]
+mod test_server;
 mod txn;
 mod types;
 mod version;
 
-pub use crate::auth::ClientCertificate;
-pub use crate::config::{Config, ConfigBuilder, Database};
+pub use crate::auth::{AuthToken, AuthTokenManager, ClientCertificate, RefreshingAuthTokenManager};
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+pub use crate::bookmarks::{BookmarkManager, InMemoryBookmarkManager};
+pub use crate::config::{Config, ConfigBuilder, Database, TransactionConfig};
+pub use crate::connection::ServerInfo;
 pub use crate::errors::{
     Error, Neo4jClientErrorKind, Neo4jError, Neo4jErrorKind, Neo4jSecurityErrorKind, Result,
 };
 pub use crate::graph::{query, Graph};
+pub use crate::metrics::{Histogram, Metrics, PoolMetrics};
 pub use crate::query::{Query, QueryParameter, RunResult};
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+pub use crate::routing::{
+    BoltServer, ClusterRoutingTableProvider, LeastInFlightStrategy, LoadBalancingStrategy,
+    RoundRobinStrategy, RoutingTable, RoutingTableProvider, Server, ServerAddressResolver,
+    ServerAddressResolverFn,
+};
 pub use crate::row::{Node, Path, Point2D, Point3D, Relation, Row, UnboundedRelation};
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+pub use crate::session::{Session, SessionConfig};
 pub use crate::stream::{DetachedRowStream, RowStream};
 pub use crate::txn::Txn;
 pub use crate::types::serde::{
-    DeError, EndNodeId, Id, Indices, Keys, Labels, Nodes, Offset, Relationships, StartNodeId,
-    Timezone, Type,
+    DeError, ElementId, EndNodeElementId, EndNodeId, Id, Indices, Keys, Labels, Nodes, Offset,
+    Relationships, StartNodeElementId, StartNodeId, Timezone, Type,
 };
 pub use crate::types::{
     BoltBoolean, BoltBytes, BoltDate, BoltDateTime, BoltDateTimeZoneId, BoltDuration, BoltFloat,
@@ -530,6 +547,7 @@ pub use crate::types::{
 pub use crate::version::Version;
 pub(crate) use messages::Success;
 use std::fmt::Display;
+pub use tokio_util::sync::CancellationToken;
 
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Operation {
@@ -5,6 +5,8 @@ mod discard;
 mod failure;
 mod hello;
 mod ignore;
+mod logoff;
+mod logon;
 mod pull;
 mod record;
 mod reset;
@@ -13,10 +15,12 @@ mod run;
 mod success;
 
 use crate::{
+    auth::AuthToken,
+    config::TransactionConfig,
     errors::{Error, Result},
     types::{BoltMap, BoltWireFormat},
     version::Version,
-    BoltString, BoltType,
+    BoltString, BoltType, Operation,
 };
 use begin::Begin;
 use bytes::Bytes;
@@ -34,6 +38,15 @@ pub enum BoltResponse {
     Record(Record),
 }
 
+/// The options of a `BEGIN` request, see [`BoltRequest::begin`].
+#[derive(Debug, Clone, Copy)]
+pub struct BeginOpts<'a> {
+    pub db: Option<&'a str>,
+    pub mode: Operation,
+    pub imp_user: Option<&'a str>,
+    pub txn_config: Option<&'a TransactionConfig>,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 #[cfg_attr(feature = "unstable-bolt-protocol-impl-v2", allow(deprecated))]
 pub enum BoltRequest {
@@ -42,6 +55,16 @@ pub enum BoltRequest {
         deprecated(since = "0.9.0", note = "Use `crate::bolt::Hello` instead.")
     )]
     Hello(hello::Hello),
+    #[cfg_attr(
+        feature = "unstable-bolt-protocol-impl-v2",
+        deprecated(since = "0.9.0", note = "Use `crate::bolt::Logon` instead.")
+    )]
+    Logon(logon::Logon),
+    #[cfg_attr(
+        feature = "unstable-bolt-protocol-impl-v2",
+        deprecated(since = "0.9.0", note = "Use `crate::bolt::Logoff` instead.")
+    )]
+    Logoff(logoff::Logoff),
     Run(Run),
     #[cfg_attr(
         feature = "unstable-bolt-protocol-impl-v2",
@@ -69,23 +92,26 @@ pub enum BoltRequest {
         deprecated(since = "0.9.0", note = "Use `crate::bolt::Reset` instead.")
     )]
     Reset(reset::Reset),
+    #[cfg_attr(
+        feature = "unstable-bolt-protocol-impl-v2",
+        deprecated(since = "0.9.0", note = "Use `crate::bolt::Goodbye` instead.")
+    )]
+    Goodbye(bye::Bye),
 }
 
 #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
-pub struct HelloBuilder {
+pub struct HelloBuilder<'a> {
     agent: BoltString,
-    principal: BoltString,
-    credentials: BoltString,
+    auth: &'a AuthToken,
     routing: Option<BoltMap>,
 }
 
 #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
-impl HelloBuilder {
-    pub fn new(principal: impl Into<BoltString>, credentials: impl Into<BoltString>) -> Self {
+impl<'a> HelloBuilder<'a> {
+    pub fn new(auth: &'a AuthToken) -> Self {
         Self {
             agent: "neo4rs".into(),
-            principal: principal.into(),
-            credentials: credentials.into(),
+            auth,
             routing: None,
         }
     }
@@ -101,11 +127,10 @@ impl HelloBuilder {
     pub fn build(self, version: Version) -> BoltRequest {
         let HelloBuilder {
             agent,
-            principal,
-            credentials,
+            auth,
             routing,
         } = self;
-        BoltRequest::hello(agent, principal, credentials, routing, version)
+        BoltRequest::hello(agent, auth, routing, version)
     }
 }
 
@@ -117,16 +142,25 @@ impl BoltRequest {
     )]
     pub fn hello(
         agent: BoltString,
-        principal: BoltString,
-        credentials: BoltString,
+        auth: &AuthToken,
         routing: Option<BoltMap>,
         version: Version,
     ) -> BoltRequest {
         let mut data = BoltMap::default();
         data.put("user_agent".into(), BoltType::String(agent));
-        data.put("scheme".into(), "basic".into());
-        data.put("principal".into(), BoltType::String(principal));
-        data.put("credentials".into(), BoltType::String(credentials));
+        // Since 5.1, authentication is sent in a separate LOGON message
+        if version < Version::V5_1 {
+            auth.insert_into(&mut data);
+        }
+        if version >= Version::V5_3 {
+            let mut bolt_agent = BoltMap::default();
+            bolt_agent.put(
+                "product".into(),
+                concat!("neo4rs/", env!("CARGO_PKG_VERSION")).into(),
+            );
+            bolt_agent.put("language".into(), "Rust".into());
+            data.put("bolt_agent".into(), BoltType::Map(bolt_agent));
+        }
         if version >= Version::V4_1 {
             if let Some(context) = routing {
                 data.put("routing".into(), BoltType::Map(context));
@@ -135,6 +169,24 @@ impl BoltRequest {
         BoltRequest::Hello(hello::Hello::new(data))
     }
 
+    #[cfg_attr(
+        feature = "unstable-bolt-protocol-impl-v2",
+        deprecated(since = "0.9.0", note = "Use `crate::bolt::Logon` instead.")
+    )]
+    pub fn logon(auth: &AuthToken) -> BoltRequest {
+        let mut data = BoltMap::default();
+        auth.insert_into(&mut data);
+        BoltRequest::Logon(logon::Logon::new(data))
+    }
+
+    #[cfg_attr(
+        feature = "unstable-bolt-protocol-impl-v2",
+        deprecated(since = "0.9.0", note = "Use `crate::bolt::Logoff` instead.")
+    )]
+    pub fn logoff() -> BoltRequest {
+        BoltRequest::Logoff(logoff::Logoff::new())
+    }
+
     pub fn run(query: &str, params: BoltMap, extra: BoltMap) -> BoltRequest {
         BoltRequest::Run(Run::new(query.into(), params, extra))
     }
@@ -167,8 +219,15 @@ impl BoltRequest {
         feature = "unstable-bolt-protocol-impl-v2",
         deprecated(since = "0.9.0", note = "Use `crate::bolt::Begin` instead.")
     )]
-    pub fn begin(db: Option<&str>) -> BoltRequest {
-        let extra = db.into_iter().map(|db| ("db".into(), db.into())).collect();
+    pub fn begin(opts: BeginOpts<'_>) -> BoltRequest {
+        let mode = if opts.mode.is_read() { "r" } else { "w" };
+        let mode = ("mode".into(), mode.into());
+        let db = opts.db.map(|db| ("db".into(), db.into()));
+        let imp_user = opts.imp_user.map(|user| ("imp_user".into(), user.into()));
+        let mut extra = db.into_iter().chain(Some(mode)).chain(imp_user).collect();
+        if let Some(txn_config) = opts.txn_config {
+            txn_config.insert_into(&mut extra);
+        }
         let begin = Begin::new(extra);
         BoltRequest::Begin(begin)
     }
@@ -196,6 +255,14 @@ impl BoltRequest {
     pub fn reset() -> BoltRequest {
         BoltRequest::Reset(reset::Reset::new())
     }
+
+    #[cfg_attr(
+        feature = "unstable-bolt-protocol-impl-v2",
+        deprecated(since = "0.9.0", note = "Use `crate::bolt::Goodbye` instead.")
+    )]
+    pub fn goodbye() -> BoltRequest {
+        BoltRequest::Goodbye(bye::Bye {})
+    }
 }
 
 impl BoltRequest {
@@ -203,6 +270,8 @@ impl BoltRequest {
     pub fn into_bytes(self, version: Version) -> Result<Bytes> {
         let bytes: Bytes = match self {
             BoltRequest::Hello(hello) => hello.into_bytes(version)?,
+            BoltRequest::Logon(logon) => logon.into_bytes(version)?,
+            BoltRequest::Logoff(logoff) => logoff.into_bytes(version)?,
             BoltRequest::Run(run) => run.into_bytes(version)?,
             BoltRequest::Pull(pull) => pull.into_bytes(version)?,
             BoltRequest::Discard(discard) => discard.into_bytes(version)?,
@@ -210,6 +279,7 @@ impl BoltRequest {
             BoltRequest::Commit(commit) => commit.into_bytes(version)?,
             BoltRequest::Rollback(rollback) => rollback.into_bytes(version)?,
             BoltRequest::Reset(reset) => reset.into_bytes(version)?,
+            BoltRequest::Goodbye(goodbye) => goodbye.into_bytes(version)?,
         };
         Ok(bytes)
     }
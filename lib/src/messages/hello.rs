@@ -23,9 +23,57 @@ impl Hello {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::version::Version;
+    use crate::{messages::BoltRequest, version::Version, AuthToken};
     use bytes::*;
 
+    fn hello_extra(version: Version) -> BoltMap {
+        let routing = BoltMap::default();
+        match BoltRequest::hello(
+            "neo4rs".into(),
+            &AuthToken::basic("user", "pass"),
+            Some(routing),
+            version,
+        ) {
+            BoltRequest::Hello(hello) => hello.extra,
+            otherwise => panic!("expected HELLO, got {otherwise:?}"),
+        }
+    }
+
+    #[test]
+    fn should_build_hello_per_version() {
+        let extra = hello_extra(Version::V4);
+        assert!(extra.value.contains_key("scheme"));
+        assert!(!extra.value.contains_key("routing"));
+
+        for version in [Version::V4_4, Version::V5_0] {
+            let extra = hello_extra(version);
+            assert_eq!(extra.get::<String>("scheme").unwrap(), "basic");
+            assert_eq!(extra.get::<String>("principal").unwrap(), "user");
+            assert_eq!(extra.get::<String>("credentials").unwrap(), "pass");
+            assert!(extra.value.contains_key("routing"));
+            assert!(!extra.value.contains_key("bolt_agent"));
+        }
+
+        for version in [Version::V5_1, Version::V5_2] {
+            let extra = hello_extra(version);
+            assert!(!extra.value.contains_key("scheme"));
+            assert!(!extra.value.contains_key("principal"));
+            assert!(!extra.value.contains_key("credentials"));
+            assert!(extra.value.contains_key("routing"));
+            assert!(!extra.value.contains_key("bolt_agent"));
+        }
+
+        for version in [Version::V5_3, Version::V5_4] {
+            let extra = hello_extra(version);
+            assert!(!extra.value.contains_key("credentials"));
+            let agent = extra.get::<BoltMap>("bolt_agent").unwrap();
+            assert_eq!(
+                agent.get::<String>("product").unwrap(),
+                concat!("neo4rs/", env!("CARGO_PKG_VERSION"))
+            );
+        }
+    }
+
     #[test]
     fn should_serialize_hello() {
         let hello = Hello::new(
@@ -0,0 +1,184 @@
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// The upper bounds of the buckets of [`Histogram`].
+const BUCKETS: [Duration; 12] = [
+    Duration::from_millis(1),
+    Duration::from_millis(5),
+    Duration::from_millis(10),
+    Duration::from_millis(25),
+    Duration::from_millis(50),
+    Duration::from_millis(100),
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_millis(2500),
+    Duration::from_secs(5),
+    Duration::from_secs(10),
+];
+
+/// A snapshot of the connection pools behind a [`crate::Graph`], see [`crate::Graph::metrics`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Metrics {
+    /// One pool for a direct connection, or one pool per cluster member when routing.
+    pub pools: Vec<PoolMetrics>,
+}
+
+impl Metrics {
+    /// The number of connections that are currently in use, across all pools.
+    pub fn in_use(&self) -> usize {
+        self.pools.iter().map(|pool| pool.in_use).sum()
+    }
+
+    /// The number of idle connections, across all pools.
+    pub fn idle(&self) -> usize {
+        self.pools.iter().map(|pool| pool.idle).sum()
+    }
+}
+
+/// A snapshot of a single connection pool.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PoolMetrics {
+    /// The address of the server, e.g. `bolt://localhost:7687`.
+    pub address: String,
+    /// The maximum number of connections, see [`crate::ConfigBuilder::max_connections`].
+    pub max_size: usize,
+    /// The number of connections that are currently handed out.
+    pub in_use: usize,
+    /// The number of open connections that wait in the pool.
+    pub idle: usize,
+    /// The number of connections that have been opened since the pool was created.
+    pub created: u64,
+    /// The number of connections that have been closed by the pool,
+    /// e.g. because they were broken or exceeded their max lifetime.
+    pub closed: u64,
+    /// The number of attempts to open a connection that failed.
+    pub failed_connection_attempts: u64,
+    /// The number of acquisitions that failed with [`crate::Error::ConnectionAcquisitionTimeout`].
+    pub acquisition_timeouts: u64,
+    /// How long it took to acquire a connection from the pool.
+    pub acquisition_wait: Histogram,
+}
+
+/// The distribution of durations, in buckets with fixed upper bounds.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Histogram {
+    /// The number of durations up to each upper bound and above the previous one,
+    /// the last bucket has the bound [`Duration::MAX`].
+    pub buckets: Vec<(Duration, u64)>,
+    /// The number of recorded durations.
+    pub count: u64,
+    /// The sum of all recorded durations.
+    pub sum: Duration,
+}
+
+impl Histogram {
+    /// The average of all recorded durations.
+    pub fn mean(&self) -> Option<Duration> {
+        let count = u32::try_from(self.count).ok().filter(|&count| count > 0)?;
+        Some(self.sum / count)
+    }
+}
+
+/// The counters of a connection pool, which are updated by its connection manager.
+#[derive(Debug, Default)]
+pub(crate) struct PoolCounters {
+    created: AtomicU64,
+    closed: AtomicU64,
+    failed_connection_attempts: AtomicU64,
+    acquisition_timeouts: AtomicU64,
+    acquisition_wait: [AtomicU64; BUCKETS.len() + 1],
+    acquisition_wait_nanos: AtomicU64,
+}
+
+impl PoolCounters {
+    pub(crate) fn record_created(&self) {
+        self.created.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_closed(&self) {
+        self.closed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_failed_connection_attempt(&self) {
+        self.failed_connection_attempts
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_acquisition_timeout(&self) {
+        self.acquisition_timeouts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_acquisition_wait(&self, wait: Duration) {
+        let bucket = BUCKETS
+            .iter()
+            .position(|&bound| wait <= bound)
+            .unwrap_or(BUCKETS.len());
+        self.acquisition_wait[bucket].fetch_add(1, Ordering::Relaxed);
+        let nanos = u64::try_from(wait.as_nanos()).unwrap_or(u64::MAX);
+        self.acquisition_wait_nanos
+            .fetch_add(nanos, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(
+        &self,
+        address: &str,
+        max_size: usize,
+        in_use: usize,
+        idle: usize,
+    ) -> PoolMetrics {
+        let buckets = BUCKETS
+            .iter()
+            .copied()
+            .chain([Duration::MAX])
+            .zip(&self.acquisition_wait)
+            .map(|(bound, count)| (bound, count.load(Ordering::Relaxed)))
+            .collect::<Vec<_>>();
+        PoolMetrics {
+            address: address.to_owned(),
+            max_size,
+            in_use,
+            idle,
+            created: self.created.load(Ordering::Relaxed),
+            closed: self.closed.load(Ordering::Relaxed),
+            failed_connection_attempts: self.failed_connection_attempts.load(Ordering::Relaxed),
+            acquisition_timeouts: self.acquisition_timeouts.load(Ordering::Relaxed),
+            acquisition_wait: Histogram {
+                count: buckets.iter().map(|(_, count)| count).sum(),
+                sum: Duration::from_nanos(self.acquisition_wait_nanos.load(Ordering::Relaxed)),
+                buckets,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_acquisition_wait_in_buckets() {
+        let counters = PoolCounters::default();
+        counters.record_acquisition_wait(Duration::from_micros(10));
+        counters.record_acquisition_wait(Duration::from_millis(1));
+        counters.record_acquisition_wait(Duration::from_millis(7));
+        counters.record_acquisition_wait(Duration::from_secs(60));
+
+        let histogram = counters
+            .snapshot("bolt://localhost:7687", 1, 0, 0)
+            .acquisition_wait;
+        assert_eq!(histogram.buckets.len(), BUCKETS.len() + 1);
+        assert_eq!(histogram.buckets[0], (Duration::from_millis(1), 2));
+        assert_eq!(histogram.buckets[1], (Duration::from_millis(5), 0));
+        assert_eq!(histogram.buckets[2], (Duration::from_millis(10), 1));
+        assert_eq!(histogram.buckets[BUCKETS.len()], (Duration::MAX, 1));
+        assert_eq!(histogram.count, 4);
+        assert_eq!(
+            histogram.sum,
+            Duration::from_secs(60) + Duration::from_micros(8010)
+        );
+        assert_eq!(histogram.mean(), Some(histogram.sum / 4));
+    }
+}
@@ -1,38 +1,123 @@
-use std::time::Duration;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
 
-use crate::auth::ConnectionTLSConfig;
+use crate::auth::{AuthTokenManager, ConnectionTLSConfig};
 use crate::{
-    config::Config,
+    config::{Config, ConnectionConfig, PoolConfig},
     connection::{Connection, ConnectionInfo},
     errors::{Error, Result},
+    metrics::{PoolCounters, PoolMetrics},
+    version::Version,
 };
 use backon::ExponentialBuilder;
-use deadpool::managed::{Manager, Metrics, Object, Pool, RecycleResult};
-use log::{info, trace};
+use deadpool::managed::{Manager, Metrics, Object, Pool, RecycleError, RecycleResult};
+use log::{debug, info, trace};
 
 pub type ConnectionPool = Pool<ConnectionManager>;
 pub type ManagedConnection = Object<ConnectionManager>;
 
+/// How long an idle connection may take to answer the liveness check.
+#[cfg(not(test))]
+const LIVENESS_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+#[cfg(test)]
+const LIVENESS_CHECK_TIMEOUT: Duration = Duration::from_millis(100);
+
 pub struct ConnectionManager {
+    address: String,
     info: ConnectionInfo,
     backoff: ExponentialBuilder,
+    pool: PoolConfig,
+    /// Connections that have been opened in advance to keep the configured number of
+    /// connections idle, which are handed to the pool when it needs a new connection.
+    spare: Mutex<Vec<Connection>>,
+    filling: AtomicBool,
+    closed: AtomicBool,
+    counters: PoolCounters,
 }
 
 impl ConnectionManager {
-    pub fn new(
+    pub(crate) fn new(
         uri: &str,
-        user: &str,
-        password: &str,
+        auth: Arc<dyn AuthTokenManager>,
         tls_config: &ConnectionTLSConfig,
+        connection: &ConnectionConfig,
+        pool: PoolConfig,
     ) -> Result<Self> {
-        let info = ConnectionInfo::new(uri, user, password, tls_config)?;
+        let info = ConnectionInfo::new(uri, auth, tls_config, connection)?;
         let backoff = backoff();
-        Ok(ConnectionManager { info, backoff })
+        Ok(ConnectionManager {
+            address: uri.to_owned(),
+            info,
+            backoff,
+            pool,
+            spare: Mutex::new(Vec::new()),
+            filling: AtomicBool::new(false),
+            closed: AtomicBool::new(false),
+            counters: PoolCounters::default(),
+        })
     }
 
     pub fn backoff(&self) -> ExponentialBuilder {
         self.backoff
     }
+
+    pub(crate) fn address(&self) -> &str {
+        &self.address
+    }
+
+    pub(crate) fn auth(&self) -> &dyn AuthTokenManager {
+        &*self.info.init.auth
+    }
+
+    async fn open(&self) -> Result<Connection> {
+        let connection = Connection::new(&self.info).await;
+        match connection {
+            Ok(_) => self.counters.record_created(),
+            Err(_) => self.counters.record_failed_connection_attempt(),
+        }
+        connection
+    }
+
+    /// Checks that `connection` can be handed out again, resetting it if `reset` is set
+    /// or if it has been idle for long enough to test its liveness.
+    async fn check(&self, connection: &mut Connection, reset: bool) -> RecycleResult<Error> {
+        if connection.is_defunct() {
+            return Err(RecycleError::message("connection is defunct"));
+        }
+        if let Some(max_lifetime) = self.pool.max_lifetime {
+            if connection.age() >= max_lifetime {
+                return Err(RecycleError::message(
+                    "connection exceeded its max lifetime",
+                ));
+            }
+        }
+        // a connection that cannot switch to the current token is not worth a round trip,
+        // later ones keep their token until they are handed out for another one
+        if connection.version() < Version::V5_1 {
+            let auth = self.info.init.auth.get_token().await?;
+            if connection.auth() != Some(&auth) {
+                return Err(RecycleError::message(
+                    "connection was authenticated with an outdated token",
+                ));
+            }
+        }
+        match self.pool.idle_before_liveness_check {
+            Some(idle) if connection.idle_time() >= idle => {
+                trace!("checking liveness of connection that was idle for {idle:?}");
+                tokio::time::timeout(LIVENESS_CHECK_TIMEOUT, connection.reset())
+                    .await
+                    .map_err(|_| RecycleError::message("connection failed the liveness check"))??;
+            }
+            _ if reset => connection.reset().await?,
+            _ => {}
+        }
+        Ok(())
+    }
 }
 
 pub(crate) fn backoff() -> ExponentialBuilder {
@@ -50,29 +135,406 @@ impl Manager for ConnectionManager {
     type Error = Error;
 
     async fn create(&self) -> Result<Self::Type, Self::Error> {
+        loop {
+            let Some(mut connection) = self.spare.lock().unwrap().pop() else {
+                break;
+            };
+            // a spare connection has not been used yet, so it does not need to be reset
+            match self.check(&mut connection, false).await {
+                Ok(()) => {
+                    trace!("handing out spare connection");
+                    return Ok(connection);
+                }
+                Err(e) => {
+                    debug!("Discarding spare connection: {e}");
+                    self.counters.record_closed();
+                }
+            }
+        }
         trace!("creating new connection");
-        Connection::new(&self.info).await
+        self.open().await
     }
 
     async fn recycle(&self, obj: &mut Self::Type, _: &Metrics) -> RecycleResult<Self::Error> {
         trace!("recycling connection");
-        Ok(obj.reset().await?)
+        self.check(obj, true).await
+    }
+
+    fn detach(&self, _: &mut Self::Type) {
+        self.counters.record_closed();
     }
 }
 
 pub fn create_pool(config: &Config) -> Result<ConnectionPool> {
     let mgr = ConnectionManager::new(
         &config.uri,
-        &config.user,
-        &config.password,
+        config.auth.clone(),
         &config.tls_config,
+        &config.connection,
+        config.pool.clone(),
     )?;
     info!(
         "creating connection pool with max size {}",
         config.max_connections
     );
-    Ok(ConnectionPool::builder(mgr)
+    let pool = ConnectionPool::builder(mgr)
         .max_size(config.max_connections)
         .build()
-        .expect("No timeouts configured"))
+        .expect("No timeouts configured");
+    fill_idle(&pool);
+    Ok(pool)
+}
+
+/// Returns a connection from `pool`, waiting at most for the configured acquisition timeout.
+pub(crate) async fn acquire(pool: &ConnectionPool) -> Result<ManagedConnection> {
+    let manager = pool.manager();
+    if manager.closed.load(Ordering::Acquire) {
+        return Err(Error::GraphClosed);
+    }
+    let start = Instant::now();
+    let connection = match manager.pool.acquisition_timeout {
+        Some(timeout) => tokio::time::timeout(timeout, pool.get())
+            .await
+            .map_err(|_| {
+                manager.counters.record_acquisition_timeout();
+                Error::ConnectionAcquisitionTimeout(timeout)
+            })??,
+        None => pool.get().await?,
+    };
+    manager.counters.record_acquisition_wait(start.elapsed());
+    fill_idle(pool);
+    Ok(connection)
+}
+
+/// Closes `pool` once all connections in use have been returned, or `timeout` has passed,
+/// saying goodbye to the server on each returned connection.
+/// Connections that are still in use by then are closed without a goodbye once they are returned.
+/// Acquiring a connection fails with [`Error::GraphClosed`] from now on.
+pub(crate) async fn close(pool: &ConnectionPool, timeout: Option<Duration>) {
+    let manager = pool.manager();
+    manager.closed.store(true, Ordering::Release);
+    let spare = std::mem::take(&mut *manager.spare.lock().unwrap());
+    for connection in spare {
+        manager.counters.record_closed();
+        if let Err(e) = connection.goodbye().await {
+            debug!("Failed to close connection: {e}");
+        }
+    }
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    loop {
+        for connection in pool.retain(|_, _| false).removed {
+            if let Err(e) = connection.goodbye().await {
+                debug!("Failed to close connection: {e}");
+            }
+        }
+        let in_use = pool.status().size;
+        if in_use == 0 {
+            break;
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            debug!("Closing the pool with {in_use} connections still in use");
+            break;
+        }
+        // wait for the connections in use to be returned
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    // a closed pool discards the connections that are returned to it
+    pool.close();
+    debug!("Closed connection pool for {}", pool.manager().address);
+}
+
+/// A snapshot of the connections and counters of `pool`.
+pub(crate) fn metrics(pool: &ConnectionPool) -> PoolMetrics {
+    let manager = pool.manager();
+    let status = pool.status();
+    let spare = manager.spare.lock().unwrap().len();
+    manager.counters.snapshot(
+        &manager.address,
+        status.max_size,
+        status.size.saturating_sub(status.available),
+        status.available + spare,
+    )
+}
+
+/// Opens connections in the background until the pool has the configured number of idle connections.
+///
+/// The new connections are kept aside as spare connections, which the pool takes
+/// before it opens a connection itself, so that the idle connections of the pool stay available.
+fn fill_idle(pool: &ConnectionPool) {
+    let manager = pool.manager();
+    let status = pool.status();
+    let spare = manager.spare.lock().unwrap().len();
+    let idle = status.available + spare;
+    if idle >= manager.pool.min_idle || manager.closed.load(Ordering::Acquire) {
+        return;
+    }
+    // limited to the connections that the pool may still open
+    let count =
+        (manager.pool.min_idle - idle).min(status.max_size.saturating_sub(status.size + spare));
+    if count == 0 {
+        return;
+    }
+    // without a runtime, the pool is filled on the first acquisition
+    let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+        return;
+    };
+    if manager.filling.swap(true, Ordering::AcqRel) {
+        return;
+    }
+
+    let pool = pool.clone();
+    runtime.spawn(async move {
+        let manager = pool.manager();
+        let connections = futures::future::join_all((0..count).map(|_| manager.open())).await;
+        let mut opened = 0;
+        for connection in connections.into_iter().flatten() {
+            let mut spare = manager.spare.lock().unwrap();
+            // the pool may have opened connections itself in the meantime
+            if manager.closed.load(Ordering::Acquire)
+                || pool.status().size + spare.len() >= pool.status().max_size
+            {
+                manager.counters.record_closed();
+                continue;
+            }
+            spare.push(connection);
+            opened += 1;
+        }
+        debug!("filled connection pool with {opened} idle connections");
+        manager.filling.store(false, Ordering::Release);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        auth::{AuthToken, RefreshingAuthTokenManager},
+        test_server::{self, TestServer, HELLO, RESET},
+        ConfigBuilder, Neo4jError,
+    };
+    use std::sync::atomic::AtomicUsize;
+
+    fn pool(
+        server: &TestServer,
+        options: impl FnOnce(ConfigBuilder) -> ConfigBuilder,
+    ) -> ConnectionPool {
+        let config = options(ConfigBuilder::default())
+            .uri(server.uri())
+            .user("neo4j")
+            .password("neo4j")
+            .build()
+            .unwrap();
+        create_pool(&config).unwrap()
+    }
+
+    fn connections(server: &TestServer) -> usize {
+        server
+            .requests()
+            .iter()
+            .filter(|request| request.signature == HELLO)
+            .count()
+    }
+
+    #[tokio::test]
+    async fn times_out_waiting_for_a_connection() {
+        let server = TestServer::start(Version::V4_4, |_| None).await;
+        let timeout = Duration::from_millis(50);
+        let pool = pool(&server, |config| {
+            config
+                .max_connections(1)
+                .connection_acquisition_timeout(timeout)
+        });
+
+        let _connection = acquire(&pool).await.unwrap();
+        let error = acquire(&pool).await.err().unwrap();
+        assert!(
+            matches!(error, Error::ConnectionAcquisitionTimeout(t) if t == timeout),
+            "{error}"
+        );
+        let metrics = metrics(&pool);
+        assert_eq!(metrics.acquisition_timeouts, 1);
+        assert_eq!(metrics.acquisition_wait.count, 1);
+        assert_eq!((metrics.in_use, metrics.idle), (1, 0));
+    }
+
+    #[tokio::test]
+    async fn replaces_connections_after_max_lifetime() {
+        let server = TestServer::start(Version::V4_4, |_| None).await;
+        let pool = pool(&server, |config| {
+            config.max_connection_lifetime(Duration::ZERO)
+        });
+
+        drop(acquire(&pool).await.unwrap());
+        drop(acquire(&pool).await.unwrap());
+
+        assert_eq!(connections(&server), 2);
+        let metrics = metrics(&pool);
+        assert_eq!((metrics.created, metrics.closed), (2, 1));
+        assert_eq!((metrics.in_use, metrics.idle), (0, 1));
+    }
+
+    #[tokio::test]
+    async fn replaces_idle_connections_that_fail_the_liveness_check() {
+        // never answers, like a connection that was dropped silently
+        let server = TestServer::start(Version::V4_4, |request| {
+            (request.signature == RESET).then(Vec::new)
+        })
+        .await;
+        let pool = pool(&server, |config| {
+            config.idle_time_before_liveness_check(Duration::ZERO)
+        });
+
+        drop(acquire(&pool).await.unwrap());
+        let connection = acquire(&pool).await.unwrap();
+
+        assert_eq!(connections(&server), 2);
+        let reset = server
+            .requests()
+            .into_iter()
+            .find(|request| request.signature == RESET)
+            .unwrap();
+        assert_eq!(reset.connection, 0);
+        drop(connection);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn does_not_check_the_liveness_of_connections_that_were_used_recently() {
+        // answers slower than the liveness check allows
+        let server = TestServer::start(Version::V4_4, |request| {
+            if request.signature == RESET {
+                tokio::task::block_in_place(|| std::thread::sleep(Duration::from_millis(200)));
+            }
+            None
+        })
+        .await;
+        let pool = pool(&server, |config| {
+            config.idle_time_before_liveness_check(Duration::from_millis(200))
+        });
+
+        let mut connection = acquire(&pool).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        connection.reset().await.unwrap();
+        drop(connection);
+        drop(acquire(&pool).await.unwrap());
+
+        assert_eq!(connections(&server), 1);
+    }
+
+    #[tokio::test]
+    async fn discards_connections_with_an_outdated_token_without_resetting_them() {
+        let server = TestServer::start(Version::V4_4, |_| None).await;
+        let calls = Arc::new(AtomicUsize::new(0));
+        let manager = RefreshingAuthTokenManager::new({
+            let calls = Arc::clone(&calls);
+            move || {
+                let call = calls.fetch_add(1, Ordering::SeqCst);
+                async move { Ok(AuthToken::bearer(format!("token-{call}"))) }
+            }
+        });
+        let config = ConfigBuilder::default()
+            .uri(server.uri())
+            .auth_manager(manager)
+            .build()
+            .unwrap();
+        let pool = create_pool(&config).unwrap();
+
+        let connection = acquire(&pool).await.unwrap();
+        let expired = Neo4jError::new(
+            "Neo.ClientError.Security.TokenExpired".to_owned(),
+            "token expired".to_owned(),
+        );
+        let token = connection.auth().unwrap().clone();
+        assert!(
+            pool.manager()
+                .auth()
+                .handle_security_error(&token, &expired)
+                .await
+        );
+        drop(connection);
+        drop(acquire(&pool).await.unwrap());
+
+        assert_eq!(connections(&server), 2);
+        assert!(server.requests().iter().all(|r| r.signature != RESET));
+    }
+
+    /// Waits until the pool has the given number of idle and used connections.
+    async fn wait_for(pool: &ConnectionPool, idle: usize, in_use: usize) {
+        for _ in 0..100 {
+            let metrics = metrics(pool);
+            if (metrics.idle, metrics.in_use) == (idle, in_use) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("unexpected pool metrics: {:?}", metrics(pool));
+    }
+
+    #[tokio::test]
+    async fn keeps_min_idle_connections_open() {
+        let server = TestServer::start(Version::V4_4, |_| None).await;
+        let pool = pool(&server, |config| {
+            config.max_connections(3).min_idle_connections(2)
+        });
+
+        wait_for(&pool, 2, 0).await;
+        let first = acquire(&pool).await.unwrap();
+        wait_for(&pool, 2, 1).await;
+        // the pool does not grow beyond its max size
+        let second = acquire(&pool).await.unwrap();
+        wait_for(&pool, 1, 2).await;
+        drop((first, second));
+        assert_eq!(connections(&server), 3);
+    }
+
+    #[tokio::test]
+    async fn filling_idle_connections_does_not_take_them_from_callers() {
+        let server = TestServer::start(Version::V4_4, |_| None).await;
+        let pool = pool(&server, |config| {
+            config
+                .max_connections(2)
+                .min_idle_connections(2)
+                .connection_acquisition_timeout(Duration::from_millis(200))
+        });
+
+        wait_for(&pool, 2, 0).await;
+        let rounds = 5;
+        for _ in 0..rounds {
+            let (first, second) = tokio::join!(acquire(&pool), acquire(&pool));
+            drop((first.unwrap(), second.unwrap()));
+        }
+
+        assert_eq!(connections(&server), 2);
+        // only the connections that are handed out again are reset
+        let resets = server
+            .requests()
+            .iter()
+            .filter(|request| request.signature == RESET)
+            .count();
+        assert_eq!(resets, 2 * rounds - 2);
+        assert_eq!(metrics(&pool).acquisition_timeouts, 0);
+    }
+
+    #[tokio::test]
+    async fn counts_failed_connection_attempts() {
+        let server = TestServer::start(Version::V4_4, |request| {
+            (request.signature == HELLO).then(|| {
+                vec![test_server::failure(
+                    "Neo.ClientError.Security.Unauthorized",
+                    "no",
+                )]
+            })
+        })
+        .await;
+        let pool = pool(&server, |config| config);
+
+        assert!(acquire(&pool).await.is_err());
+
+        let metrics = metrics(&pool);
+        assert_eq!(metrics.address, server.uri());
+        assert_eq!(
+            (metrics.created, metrics.failed_connection_attempts),
+            (0, 1)
+        );
+        assert_eq!(metrics.acquisition_wait.count, 0);
+    }
 }
@@ -1,8 +1,12 @@
-use std::cell::{Cell, RefCell};
+use std::{
+    cell::{Cell, RefCell},
+    future::{self, Future},
+    time::Duration,
+};
 
-#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
-use crate::{bolt::Summary, summary::ResultSummary};
 use crate::{
+    auth::{AuthToken, AuthTokenManager},
+    config::TransactionConfig,
     errors::Result,
     graph::ConnectionPoolManager,
     messages::{BoltRequest, BoltResponse},
@@ -10,8 +14,12 @@ use crate::{
     retry::Retry,
     stream::{DetachedRowStream, RowStream},
     types::{BoltList, BoltMap, BoltString, BoltType},
-    Database, Error, Operation, Success,
+    Database, Error, Neo4jClientErrorKind, Neo4jError, Neo4jErrorKind, Neo4jSecurityErrorKind,
+    Operation, Success,
 };
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+use crate::{bolt::Summary, summary::ResultSummary};
+use tokio_util::sync::CancellationToken;
 
 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
 pub type RunResult = ResultSummary;
@@ -24,6 +32,7 @@ pub struct Query {
     query: String,
     params: BoltMap,
     extra: BoltMap,
+    interrupt: Interrupt,
 }
 
 impl Query {
@@ -32,6 +41,7 @@ impl Query {
             query,
             params: BoltMap::default(),
             extra: BoltMap::default(),
+            interrupt: Interrupt::default(),
         }
     }
 
@@ -74,6 +84,47 @@ impl Query {
         self
     }
 
+    /// Fails the query with [`Error::Timeout`] if the server does not complete it within `timeout`.
+    ///
+    /// Unlike the timeout of a [`TransactionConfig`], which is enforced by the server,
+    /// this also covers a server that stopped responding.
+    /// The timeout applies to every attempt to run the query, and for a query that is
+    /// executed into a stream it ends once the stream is returned.
+    /// The server is told to stop working on a query that timed out with a `RESET`.
+    /// In a [`crate::Txn`], this also rolls back the transaction,
+    /// whose later queries and commit fail with [`Error::TransactionTerminated`].
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.interrupt.timeout = Some(timeout);
+        self
+    }
+
+    /// Fails the query with [`Error::Cancelled`] once `token` is cancelled,
+    /// including while it waits for a connection.
+    ///
+    /// The server is told to stop working on a cancelled query with a `RESET`,
+    /// a connection that cannot be recovered is discarded.
+    /// In a [`crate::Txn`], this also rolls back the transaction,
+    /// whose later queries and commit fail with [`Error::TransactionTerminated`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use neo4rs::{query, CancellationToken, Error, Graph};
+    ///
+    /// # async fn example(graph: Graph) {
+    /// let token = CancellationToken::new();
+    /// let q = query("CALL apoc.util.sleep(60000)").with_cancellation(token.clone());
+    /// let run = tokio::spawn(async move { graph.run(q).await });
+    ///
+    /// token.cancel();
+    /// assert!(matches!(run.await.unwrap(), Err(Error::Cancelled)));
+    /// # }
+    /// ```
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.interrupt.cancellation = Some(token);
+        self
+    }
+
     pub fn has_param_key(&self, key: &str) -> bool {
         self.params.value.contains_key(key)
     }
@@ -92,28 +143,42 @@ impl Query {
 
     pub(crate) async fn run(self, connection: &mut ManagedConnection) -> Result<RunResult> {
         let request = BoltRequest::run(&self.query, self.params, self.extra);
-        Self::try_run(request, connection)
+        Self::try_run(request, &self.interrupt, connection)
             .await
             .map_err(Retry::into_inner)
     }
 
-    pub(crate) fn into_retryable(
+    /// Sets the timeout and metadata of the implicit transaction of this query.
+    pub(crate) fn with_txn_config(mut self, txn_config: &TransactionConfig) -> Self {
+        txn_config.insert_into(&mut self.extra);
+        self
+    }
+
+    pub(crate) fn into_retryable<'a>(
         self,
         db: Option<Database>,
+        imp_user: Option<&str>,
         operation: Operation,
-        pool: &ConnectionPoolManager,
+        pool: &'a ConnectionPoolManager,
+        auth: Option<&'a AuthToken>,
         fetch_size: Option<usize>,
-    ) -> RetryableQuery<'_> {
+    ) -> RetryableQuery<'a> {
         let query = match db.as_deref() {
             Some(db) => self.extra("db", db),
             None => self,
         };
+        let query = match imp_user {
+            Some(imp_user) => query.extra("imp_user", imp_user),
+            None => query,
+        };
 
         let is_read = operation.is_read();
         let query = query.extra("mode", if is_read { "r" } else { "w" });
 
         RetryableQuery {
             pool,
+            auth,
+            db,
             query,
             operation,
             fetch_size,
@@ -125,18 +190,16 @@ impl Query {
         connection: &mut ManagedConnection,
     ) -> QueryResult<RunResult> {
         let request = BoltRequest::run(&self.query, self.params.clone(), self.extra.clone());
-        Self::try_run(request, connection).await
+        Self::try_run(request, &self.interrupt, connection).await
     }
 
     pub(crate) async fn execute_retryable(
         &self,
         fetch_size: usize,
-        mut connection: ManagedConnection,
-    ) -> QueryResult<DetachedRowStream> {
+        connection: &mut ManagedConnection,
+    ) -> QueryResult<RowStream> {
         let request = BoltRequest::run(&self.query, self.params.clone(), self.extra.clone());
-        Self::try_execute(request, fetch_size, &mut connection)
-            .await
-            .map(|stream| DetachedRowStream::new(stream, connection))
+        Self::try_execute(request, fetch_size, &self.interrupt, connection).await
     }
 
     pub(crate) async fn execute_mut(
@@ -145,20 +208,47 @@ impl Query {
         connection: &mut ManagedConnection,
     ) -> Result<RowStream> {
         let run = BoltRequest::run(&self.query, self.params, self.extra);
-        Self::try_execute(run, fetch_size, connection)
+        Self::try_execute(run, fetch_size, &self.interrupt, connection)
             .await
             .map_err(Retry::into_inner)
     }
 
     async fn try_run(
         request: BoltRequest,
+        interrupt: &Interrupt,
         connection: &mut ManagedConnection,
     ) -> QueryResult<RunResult> {
-        let result = Self::try_execute(request, 4096, connection).await?;
-        Ok(result.finish(connection).await?)
+        let run = async {
+            let connection = &mut *connection;
+            let result = Self::try_start(request, 4096, connection).await?;
+            Ok(result.finish(connection).await?)
+        };
+        match interrupt.wait_for(run).await {
+            Ok(result) => result,
+            Err(error) => Err(Self::interrupted(connection, error).await),
+        }
     }
 
     async fn try_execute(
+        request: BoltRequest,
+        fetch_size: usize,
+        interrupt: &Interrupt,
+        connection: &mut ManagedConnection,
+    ) -> QueryResult<RowStream> {
+        let start = Self::try_start(request, fetch_size, connection);
+        match interrupt.wait_for(start).await {
+            Ok(result) => result,
+            Err(error) => Err(Self::interrupted(connection, error).await),
+        }
+    }
+
+    /// Stops the server from working on a query that timed out or was cancelled.
+    async fn interrupted(connection: &mut ManagedConnection, error: Error) -> Retry<Error> {
+        connection.interrupt().await;
+        Retry::no(error)
+    }
+
+    async fn try_start(
         request: BoltRequest,
         fetch_size: usize,
         connection: &mut ManagedConnection,
@@ -212,16 +302,48 @@ impl std::fmt::Debug for Query {
     }
 }
 
+/// Ends a query early, see [`Query::with_timeout`] and [`Query::with_cancellation`].
+#[derive(Clone, Default)]
+struct Interrupt {
+    timeout: Option<Duration>,
+    cancellation: Option<CancellationToken>,
+}
+
+impl Interrupt {
+    /// Waits for `work`, unless the query times out or is cancelled first.
+    async fn wait_for<T>(&self, work: impl Future<Output = T>) -> Result<T> {
+        let timeout = async {
+            match self.timeout {
+                Some(timeout) => {
+                    tokio::time::sleep(timeout).await;
+                    Error::Timeout(timeout)
+                }
+                None => future::pending().await,
+            }
+        };
+        tokio::select! {
+            biased;
+            error = self.cancelled() => Err(error),
+            error = timeout => Err(error),
+            result = work => Ok(result),
+        }
+    }
+
+    /// Resolves once the query is cancelled.
+    async fn cancelled(&self) -> Error {
+        match &self.cancellation {
+            Some(token) => token.cancelled().await,
+            None => future::pending().await,
+        }
+        Error::Cancelled
+    }
+}
+
 pub(crate) type QueryResult<T> = Result<T, Retry<Error>>;
 
 fn wrap_error<T>(resp: impl IntoError, req: &'static str) -> QueryResult<T> {
     let error = resp.into_error(req);
-    let can_retry = match &error {
-        Error::Neo4j(e) => e.can_retry(),
-        _ => false,
-    };
-
-    if can_retry {
+    if error.can_retry() {
         Err(Retry::yes(error))
     } else {
         Err(Retry::no(error))
@@ -230,17 +352,14 @@ fn wrap_error<T>(resp: impl IntoError, req: &'static str) -> QueryResult<T> {
 
 pub(crate) struct RetryableQuery<'a> {
     pool: &'a ConnectionPoolManager,
+    auth: Option<&'a AuthToken>,
+    db: Option<Database>,
     query: Query,
     operation: Operation,
     fetch_size: Option<usize>,
 }
 
 impl<'a> RetryableQuery<'a> {
-    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
-    pub(crate) fn is_read(&self) -> bool {
-        self.operation.is_read()
-    }
-
     pub(crate) async fn retry_run(self) -> (Self, QueryResult<RunResult>) {
         let result = self.run().await;
         (self, result)
@@ -248,7 +367,10 @@ impl<'a> RetryableQuery<'a> {
 
     async fn run(&self) -> QueryResult<RunResult> {
         let mut connection = self.connect().await?;
-        self.query.run_retryable(&mut connection).await
+        match self.query.run_retryable(&mut connection).await {
+            Ok(result) => Ok(result),
+            Err(e) => Err(handle_expired_auth(connection, self.auth, e).await),
+        }
     }
 
     pub(crate) async fn retry_execute(self) -> (Self, QueryResult<DetachedRowStream>) {
@@ -262,15 +384,105 @@ impl<'a> RetryableQuery<'a> {
             "Calling execute requires a fetch_size"
         );
 
-        let connection = self.connect().await?;
-        self.query
-            .execute_retryable(self.fetch_size.expect("fetch_size must be set"), connection)
+        let mut connection = self.connect().await?;
+        match self
+            .query
+            .execute_retryable(
+                self.fetch_size.expect("fetch_size must be set"),
+                &mut connection,
+            )
             .await
+        {
+            Ok(stream) => Ok(DetachedRowStream::new(stream, connection)),
+            Err(e) => Err(handle_expired_auth(connection, self.auth, e).await),
+        }
     }
 
     async fn connect(&self) -> QueryResult<ManagedConnection> {
-        // an error when retrieving a connection is considered permanent
-        self.pool.get(Some(self.operation)).await.map_err(Retry::No)
+        let manager = match self.auth {
+            Some(auth) => auth as &dyn AuthTokenManager,
+            None => self.pool.auth(),
+        };
+        // the token that a new connection is authenticated with, in case it has expired
+        let token = manager.get_token().await.ok();
+        let get = self
+            .pool
+            .get(self.db.as_ref(), Some(self.operation), self.auth);
+        let connection = tokio::select! {
+            biased;
+            error = self.query.interrupt.cancelled() => Err(error),
+            connection = get => connection,
+        };
+        let connection = match connection {
+            Ok(connection) => connection,
+            Err(e) => {
+                let retry = match (expired_auth(&e), &token) {
+                    // retried if the manager handled it, like a query that failed
+                    (Some(error), Some(token)) => manager.handle_security_error(token, error).await,
+                    (Some(_), None) => false,
+                    // any other error is considered permanent, unless it timed out
+                    (None, _) => e.can_retry(),
+                };
+                return Err(if retry { Retry::yes(e) } else { Retry::no(e) });
+            }
+        };
+        if self.query.has_extra_key("imp_user") {
+            connection.check_impersonation().map_err(Retry::No)?;
+        }
+        Ok(connection)
+    }
+}
+
+/// Failures due to expired credentials are reported to the [`crate::AuthTokenManager`],
+/// and retried if the manager handled them, e.g. by refreshing the token.
+/// A token given with [`crate::Graph::with_auth`] is treated like a manager that never refreshes.
+/// The connection is removed from the pool, as its authentication is no longer valid.
+async fn handle_expired_auth(
+    connection: ManagedConnection,
+    auth: Option<&AuthToken>,
+    error: Retry<Error>,
+) -> Retry<Error> {
+    let Some(neo4j_error) = expired_auth(error.inner()) else {
+        return error;
+    };
+    let Some(pool) = ManagedConnection::pool(&connection) else {
+        return error;
+    };
+
+    let connection = ManagedConnection::take(connection);
+    let handled = match connection.auth() {
+        Some(token) => {
+            let manager = match auth {
+                Some(auth) => auth as &dyn AuthTokenManager,
+                None => pool.manager().auth(),
+            };
+            manager.handle_security_error(token, neo4j_error).await
+        }
+        None => false,
+    };
+
+    if handled {
+        Retry::yes(error.into_inner())
+    } else {
+        Retry::no(error.into_inner())
+    }
+}
+
+/// The failure of `error` if it is due to expired credentials.
+fn expired_auth(error: &Error) -> Option<&Neo4jError> {
+    match error {
+        Error::Neo4j(e)
+            if matches!(
+                e.kind(),
+                Neo4jErrorKind::Client(Neo4jClientErrorKind::Security(
+                    Neo4jSecurityErrorKind::TokenExpired
+                        | Neo4jSecurityErrorKind::AuthorizationExpired
+                ))
+            ) =>
+        {
+            Some(e)
+        }
+        _ => None,
     }
 }
 
@@ -478,4 +690,112 @@ mod tests {
         assert!(q.has_param_key("name"));
         assert!(!q.has_param_key("country"));
     }
+
+    #[tokio::test]
+    async fn retries_with_refreshed_token_after_token_expired() {
+        use crate::{
+            auth::{AuthToken, RefreshingAuthTokenManager},
+            config::ConfigBuilder,
+            graph::Graph,
+            test_server::{self, TestServer, HELLO, RUN},
+            version::Version,
+        };
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let server = TestServer::start(Version::V4_4, |request| {
+            // the first connection is authenticated with the expired token
+            (request.signature == RUN && request.connection == 0).then(|| {
+                vec![test_server::failure(
+                    "Neo.ClientError.Security.TokenExpired",
+                    "token expired",
+                )]
+            })
+        })
+        .await;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let manager = RefreshingAuthTokenManager::new({
+            let calls = Arc::clone(&calls);
+            move || {
+                let call = calls.fetch_add(1, Ordering::SeqCst);
+                async move { Ok(AuthToken::bearer(format!("token-{call}"))) }
+            }
+        });
+        let config = ConfigBuilder::default()
+            .uri(server.uri())
+            .auth_manager(manager)
+            .build()
+            .unwrap();
+        let graph = Graph::connect(config).unwrap();
+
+        graph.run("RETURN 1").await.unwrap();
+
+        let credentials = server
+            .requests()
+            .into_iter()
+            .filter(|request| request.signature == HELLO)
+            .map(|request| request.map(0).get::<String>("credentials").unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(credentials, ["token-0", "token-1"]);
+    }
+
+    #[tokio::test]
+    async fn retries_with_refreshed_token_after_hello_failed_with_token_expired() {
+        use crate::{
+            auth::{AuthToken, RefreshingAuthTokenManager},
+            config::ConfigBuilder,
+            graph::Graph,
+            test_server::{self, TestServer, HELLO},
+            version::Version,
+        };
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let server = TestServer::start(Version::V4_4, |request| {
+            let expired = request.signature == HELLO
+                && request
+                    .map(0)
+                    .get::<String>("credentials")
+                    .is_ok_and(|token| token == "token-0");
+            expired.then(|| {
+                vec![test_server::failure(
+                    "Neo.ClientError.Security.TokenExpired",
+                    "token expired",
+                )]
+            })
+        })
+        .await;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let manager = RefreshingAuthTokenManager::new({
+            let calls = Arc::clone(&calls);
+            move || {
+                let call = calls.fetch_add(1, Ordering::SeqCst);
+                async move { Ok(AuthToken::bearer(format!("token-{call}"))) }
+            }
+        });
+        let config = ConfigBuilder::default()
+            .uri(server.uri())
+            .auth_manager(manager)
+            .build()
+            .unwrap();
+        let graph = Graph::connect(config).unwrap();
+
+        graph.run("RETURN 1").await.unwrap();
+        graph.run("RETURN 1").await.unwrap();
+
+        let credentials = server
+            .requests()
+            .into_iter()
+            .filter(|request| request.signature == HELLO)
+            .map(|request| request.map(0).get::<String>("credentials").unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(credentials, ["token-0", "token-1"]);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
 }
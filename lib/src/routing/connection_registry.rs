@@ -1,38 +1,87 @@
+use crate::auth::{AuthToken, AuthTokenManager};
 use crate::connection::NeoUrl;
 use crate::pool::{create_pool, ConnectionPool};
 use crate::routing::routing_table_provider::RoutingTableProvider;
-use crate::routing::Server;
-use crate::{Config, Error};
+use crate::routing::{seed_routers, RoutingTable, Server};
+use crate::{Config, Database, Error};
 use dashmap::DashMap;
-use log::debug;
+use log::{debug, warn};
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc::Sender;
-use tokio::sync::{mpsc, Mutex};
+use tokio::sync::{mpsc, Mutex, Notify};
+use tokio::time::Instant;
 
 /// Represents a Bolt server, with its address, port and role.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub(crate) struct BoltServer {
+///
+/// Two servers are equal if they have the same address, port and role,
+/// regardless of the connections that are in use.
+#[derive(Debug, Clone)]
+pub struct BoltServer {
     pub(crate) address: String,
     pub(crate) port: u16,
     pub(crate) role: String,
+    /// The number of connections to the server that are currently in use,
+    /// as of when the server was handed to a [`crate::LoadBalancingStrategy`].
+    pub(crate) in_flight: usize,
+}
+
+impl PartialEq for BoltServer {
+    fn eq(&self, other: &Self) -> bool {
+        (&self.address, self.port, &self.role) == (&other.address, other.port, &other.role)
+    }
+}
+
+impl Eq for BoltServer {}
+
+impl Hash for BoltServer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (&self.address, self.port, &self.role).hash(state);
+    }
 }
 
 impl BoltServer {
-    pub(crate) fn resolve(server: &Server) -> Vec<Self> {
+    /// The host name or IP address of the server.
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// The Bolt port of the server.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// The role of the server in the routing table, `ROUTE`, `READ` or `WRITE`.
+    pub fn role(&self) -> &str {
+        &self.role
+    }
+
+    /// The number of connections to the server that are currently in use.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight
+    }
+
+    /// The servers at the addresses of `server`, which fails if an address cannot be parsed.
+    pub(crate) fn resolve(server: &Server) -> Result<Vec<Self>, Error> {
         server
             .addresses
             .iter()
             .map(|address| {
-                let bs = NeoUrl::parse(address)
-                    .map(|addr| BoltServer {
-                        address: addr.host().to_string(),
-                        port: addr.port(),
-                        role: server.role.to_string(),
-                    })
-                    .unwrap_or_else(|_| panic!("Failed to parse address {}", address));
+                let url = NeoUrl::parse(address).map_err(|e| {
+                    Error::RoutingTableRefreshFailed(format!(
+                        "Invalid address `{address}` in the routing table: {e}"
+                    ))
+                })?;
+                let bs = BoltServer {
+                    address: url.host().to_string(),
+                    port: url.port(),
+                    role: server.role.to_string(),
+                    in_flight: 0,
+                };
                 debug!("Resolved server: {:?}", bs);
-                bs
+                Ok(bs)
             })
             .collect()
     }
@@ -41,14 +90,50 @@ impl BoltServer {
 /// A registry of connection pools, indexed by the Bolt server they connect to.
 pub type Registry = DashMap<BoltServer, ConnectionPool>;
 
-#[derive(Clone)]
+/// The servers of a database, see [`ConnectionRegistry::routing_tables`].
+struct RoutingTableEntry {
+    servers: Vec<BoltServer>,
+    ttl: Duration,
+    /// When the routing table must be fetched again before it is used.
+    expires_at: Instant,
+    /// When the background updater fetches the routing table again,
+    /// which is postponed by one TTL if fetching it failed.
+    refresh_at: Instant,
+}
+
+/// The home database of a user, see [`ConnectionRegistry::home_dbs`].
+struct HomeDbEntry {
+    db: Option<Database>,
+    expires_at: Instant,
+}
+
+/// A database, `None` for the home database, and the user that it is fetched for,
+/// `None` for the configured one.
+type RefreshKey = (Option<Database>, Option<String>);
+
+/// The principal of the credentials that the home database is resolved with,
+/// `None` for the configured ones, and the impersonated user, if any.
+type HomeDbKey = (Option<String>, Option<String>);
+
 pub(crate) struct ConnectionRegistry {
     pub(crate) connections: Registry,
+    /// The routing table of every database that has been used,
+    /// `None` for the home database of a server that does not name it.
+    routing_tables: DashMap<Option<Database>, RoutingTableEntry>,
+    /// The home database of the configured user, of a user that authenticated with other credentials,
+    /// or of an impersonated user, which is cached for the TTL of its routing table.
+    home_dbs: DashMap<HomeDbKey, HomeDbEntry>,
+    /// When and why the last refresh of the routing table of a database failed for a user,
+    /// until it is fetched successfully.
+    failures: DashMap<RefreshKey, (Instant, String)>,
+    /// Lets concurrent callers wait for a single fetch of an expired routing table,
+    /// while the routing tables of other databases or users are fetched independently.
+    refreshing: DashMap<RefreshKey, Arc<Mutex<()>>>,
+    /// Wakes up the background updater when a routing table has been fetched.
+    updated: Notify,
 }
 
-#[allow(dead_code)]
 pub(crate) enum RegistryCommand {
-    Refresh(Vec<String>),
     Stop,
 }
 
@@ -56,32 +141,59 @@ impl Default for ConnectionRegistry {
     fn default() -> Self {
         ConnectionRegistry {
             connections: Registry::new(),
+            routing_tables: DashMap::new(),
+            home_dbs: DashMap::new(),
+            failures: DashMap::new(),
+            refreshing: DashMap::new(),
+            updated: Notify::new(),
         }
     }
 }
 
+/// Fetches the routing table of `config.db` and creates the pools of its new servers.
+/// Without a database, the server answers with the routing table of the home database of
+/// the user, or of `imp_user`, which is stored under the name of that database.
+/// The routers are asked with the credentials of `auth`, if any, instead of the configured ones.
+///
+/// A failure to reach any router is recorded, so that the callers that waited for
+/// this refresh fail with the same error, see [`ConnectionRegistry::failed_since`].
 async fn refresh_routing_table(
     config: Config,
     registry: Arc<ConnectionRegistry>,
     provider: Arc<dyn RoutingTableProvider>,
     bookmarks: &[String],
-) -> Result<u64, Error> {
-    debug!("Routing table expired or empty, refreshing...");
-    let routing_table = provider.fetch_routing_table(&config, bookmarks).await?;
+    auth: Option<&Arc<AuthToken>>,
+    imp_user: Option<&str>,
+) -> Result<RoutingTable, Error> {
+    debug!(
+        "Routing table of {:?} expired or empty, refreshing...",
+        config.db
+    );
+    let key = (config.db.clone(), imp_user.map(str::to_owned));
+    let scheme = pool_scheme(&config.uri)?;
+    // the pools of the servers keep using the configured credentials
+    let routers = match auth {
+        Some(auth) => Config {
+            auth: Arc::clone(auth) as Arc<dyn AuthTokenManager>,
+            ..config.clone()
+        },
+        None => config.clone(),
+    };
+    let (routing_table, servers) =
+        match fetch_from_routers(&routers, &registry, &provider, bookmarks, imp_user).await {
+            Ok(fetched) => fetched,
+            Err(Error::RoutingTableRefreshFailed(reason)) => {
+                registry
+                    .failures
+                    .insert(key, (Instant::now(), reason.clone()));
+                return Err(Error::RoutingTableRefreshFailed(reason));
+            }
+            Err(e) => return Err(e),
+        };
     debug!(
         "Routing table refreshed: {:?} (bookmarks: {:?})",
         routing_table, bookmarks
     );
-    let servers = routing_table.resolve();
-    let url = NeoUrl::parse(config.uri.as_str())?;
-    // Convert neo4j scheme to bolt scheme to create connection pools.
-    // We need to use the bolt scheme since we don't want new connections to be routed
-    let scheme = match url.scheme() {
-        "neo4j" => "bolt",
-        "neo4j+s" => "bolt+s",
-        "neo4j+ssc" => "bolt+ssc",
-        _ => panic!("Unsupported scheme: {}", url.scheme()),
-    };
 
     for server in servers.iter() {
         if registry.connections.contains_key(server) {
@@ -97,13 +209,232 @@ async fn refresh_routing_table(
             })?,
         );
     }
-    registry.connections.retain(|k, _| servers.contains(k));
+    let ttl = Duration::from_secs(routing_table.ttl);
+    let expires_at = Instant::now() + ttl;
+    let db = routing_table.db.clone().or(config.db.clone());
+    registry.failures.remove(&key);
+    registry.routing_tables.insert(
+        db,
+        RoutingTableEntry {
+            servers,
+            ttl,
+            expires_at,
+            refresh_at: expires_at,
+        },
+    );
+    registry.retain_routed_pools();
+    registry.updated.notify_one();
     debug!(
         "Registry updated. New size is {} with TTL {}s",
         registry.connections.len(),
         routing_table.ttl
     );
-    Ok(routing_table.ttl)
+    Ok(routing_table)
+}
+
+/// The scheme of the pools of the routed servers, which are connected to directly,
+/// since the connections of a pool must not be routed again.
+fn pool_scheme(uri: &str) -> Result<&'static str, Error> {
+    match NeoUrl::parse(uri)?.scheme() {
+        "neo4j" => Ok("bolt"),
+        "neo4j+s" => Ok("bolt+s"),
+        "neo4j+ssc" => Ok("bolt+ssc"),
+        scheme => Err(Error::RoutingTableRefreshFailed(format!(
+            "Routing is not supported with the scheme `{scheme}`, use `neo4j`, `neo4j+s` or `neo4j+ssc`"
+        ))),
+    }
+}
+
+/// Asks the routers of the current routing table of `config.db` for a new one, one after the other,
+/// and falls back to the routers that the seed uri resolves to once all of them failed.
+///
+/// Fails with [`Error::RoutingTableRefreshFailed`] and the reasons of all routers if none answered
+/// with a valid routing table, or right away if the database does not exist.
+async fn fetch_from_routers(
+    config: &Config,
+    registry: &ConnectionRegistry,
+    provider: &Arc<dyn RoutingTableProvider>,
+    bookmarks: &[String],
+    imp_user: Option<&str>,
+) -> Result<(RoutingTable, Vec<BoltServer>), Error> {
+    let scheme = NeoUrl::parse(&config.uri)?.scheme().to_owned();
+    let known = registry
+        .routers(config.db.as_ref())
+        .into_iter()
+        .map(|router| Config {
+            uri: format!("{}://{}:{}", scheme, router.address, router.port),
+            ..config.clone()
+        })
+        .collect::<Vec<_>>();
+    let mut failures = Vec::new();
+    for router in &known {
+        match fetch_from_router(router, provider, bookmarks, imp_user).await {
+            Ok(fetched) => return Ok(fetched),
+            Err(e) if e.is_fatal_discovery() => return Err(e),
+            Err(e) => failures.push(format!("`{}`: {}", router_name(router), e)),
+        }
+    }
+
+    if !known.is_empty() {
+        warn!(
+            "All routers of {:?} failed, falling back to the seed router",
+            config.db
+        );
+    }
+    match seed_routers(config).await {
+        Ok(seeds) => {
+            let is_known = |seed: &Config| {
+                seed.connect_address.is_none() && known.iter().any(|router| router.uri == seed.uri)
+            };
+            for router in seeds.iter().filter(|seed| !is_known(seed)) {
+                match fetch_from_router(router, provider, bookmarks, imp_user).await {
+                    Ok(fetched) => return Ok(fetched),
+                    Err(e) if e.is_fatal_discovery() => return Err(e),
+                    Err(e) => failures.push(format!("`{}`: {}", router_name(router), e)),
+                }
+            }
+        }
+        Err(e) => failures.push(format!("`{}`: {}", config.uri, e)),
+    }
+    Err(Error::RoutingTableRefreshFailed(format!(
+        "Failed to fetch the routing table of {:?} from any router: {}",
+        config.db,
+        failures.join(", ")
+    )))
+}
+
+/// The uri of a router, with the address that it has been resolved to, if any.
+fn router_name(router: &Config) -> String {
+    match &router.connect_address {
+        Some(address) => format!("{} ({})", router.uri, address),
+        None => router.uri.clone(),
+    }
+}
+
+/// Asks the router at the uri of `router` for the routing table and parses the addresses of its servers.
+async fn fetch_from_router(
+    router: &Config,
+    provider: &Arc<dyn RoutingTableProvider>,
+    bookmarks: &[String],
+    imp_user: Option<&str>,
+) -> Result<(RoutingTable, Vec<BoltServer>), Error> {
+    let name = router_name(router);
+    let routing_table = provider
+        .fetch_routing_table(router, bookmarks, imp_user)
+        .await
+        .map_err(|e| {
+            warn!("Failed to fetch the routing table from `{}`: {}", name, e);
+            e
+        })?;
+    let servers = routing_table.resolve().map_err(|e| {
+        warn!("Router `{}` sent an invalid routing table: {}", name, e);
+        e
+    })?;
+    Ok((routing_table, servers))
+}
+
+/// Fetches the routing table of `db` if it has not been used before or has expired.
+pub(crate) async fn refresh_if_expired(
+    config: &Config,
+    registry: &Arc<ConnectionRegistry>,
+    provider: &Arc<dyn RoutingTableProvider>,
+    db: Option<&Database>,
+) -> Result<(), Error> {
+    if !registry.is_expired(db) {
+        return Ok(());
+    }
+    let key = (db.cloned(), None);
+    let waiting_since = Instant::now();
+    let refreshing = registry.refresh_lock(&key);
+    let _refreshing = refreshing.lock().await;
+    // another caller may have fetched the routing table while we waited
+    if !registry.is_expired(db) {
+        return Ok(());
+    }
+    // or failed to, which would fail again right away
+    if let Some(e) = registry.failed_since(&key, waiting_since) {
+        return Err(e);
+    }
+    let bookmarks = config
+        .bookmark_manager
+        .get_bookmarks(db.map(|db| &**db))
+        .await?;
+    let config = Config {
+        db: db.cloned(),
+        ..config.clone()
+    };
+    refresh_routing_table(
+        config,
+        Arc::clone(registry),
+        Arc::clone(provider),
+        &bookmarks,
+        None,
+        None,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Resolves the home database of the user, or of `imp_user`, from the `db` of the routing table
+/// that the server sends when no database is given, which is cached until the routing table expires.
+/// The user is the one of `auth`, if given, or else the configured one.
+/// Returns `None` if the server does not name the home database.
+pub(crate) async fn resolve_home_db(
+    config: &Config,
+    registry: &Arc<ConnectionRegistry>,
+    provider: &Arc<dyn RoutingTableProvider>,
+    auth: Option<&Arc<AuthToken>>,
+    imp_user: Option<&str>,
+) -> Result<Option<Database>, Error> {
+    let user = imp_user.map(str::to_owned);
+    // credentials without a principal, e.g. a bearer token, do not tell whose home database it is
+    let home = match auth.map(|auth| auth.principal()) {
+        None => Some((None, user.clone())),
+        Some(Some(principal)) => Some((Some(principal.to_owned()), user.clone())),
+        Some(None) => None,
+    };
+    if let Some(db) = home.as_ref().and_then(|home| registry.cached_home_db(home)) {
+        return Ok(db);
+    }
+    let key = (None, user.clone());
+    let waiting_since = Instant::now();
+    let refreshing = registry.refresh_lock(&key);
+    let _refreshing = refreshing.lock().await;
+    // another caller may have resolved the home database while we waited
+    if let Some(db) = home.as_ref().and_then(|home| registry.cached_home_db(home)) {
+        return Ok(db);
+    }
+    if let Some(e) = registry.failed_since(&key, waiting_since) {
+        return Err(e);
+    }
+    let bookmarks = config.bookmark_manager.get_bookmarks(None).await?;
+    let config = Config {
+        db: None,
+        ..config.clone()
+    };
+    let routing_table = refresh_routing_table(
+        config,
+        Arc::clone(registry),
+        Arc::clone(provider),
+        &bookmarks,
+        auth,
+        imp_user,
+    )
+    .await?;
+    debug!(
+        "Resolved home database of {:?}: {:?}",
+        imp_user, routing_table.db
+    );
+    if let Some(home) = home {
+        registry.home_dbs.insert(
+            home,
+            HomeDbEntry {
+                db: routing_table.db.clone(),
+                expires_at: Instant::now() + Duration::from_secs(routing_table.ttl),
+            },
+        );
+    }
+    Ok(routing_table.db)
 }
 
 pub(crate) fn start_background_updater(
@@ -111,49 +442,26 @@ pub(crate) fn start_background_updater(
     registry: Arc<ConnectionRegistry>,
     provider: Arc<dyn RoutingTableProvider>,
 ) -> Sender<RegistryCommand> {
-    let config_clone = config.clone();
+    let config = config.clone();
     let (tx, mut rx) = mpsc::channel(1);
-    let bookmarks = Mutex::new(vec![]);
-    // This thread is in charge of refreshing the routing table periodically
+    // This thread is in charge of refreshing the routing tables once they expire,
+    // the routing table of a database is fetched when it is used for the first time
     tokio::spawn(async move {
-        let mut ttl = refresh_routing_table(
-            config_clone.clone(),
-            registry.clone(),
-            provider.clone(),
-            bookmarks.lock().await.as_slice(),
-        )
-        .await
-        .expect("Failed to get routing table. Exiting...");
-        debug!("Starting background updater with TTL: {}", ttl);
-        let mut interval = tokio::time::interval(Duration::from_secs(ttl));
-        interval.tick().await; // first tick is immediate
         loop {
+            let refresh_at = registry.next_refresh();
             tokio::select! {
-                // Trigger periodic updates
-                _ = interval.tick() => {
-                    ttl = match refresh_routing_table(config_clone.clone(), registry.clone(), provider.clone(), bookmarks.lock().await.as_slice()).await {
-                        Ok(ttl) => ttl,
-                        Err(e) => {
-                            debug!("Failed to refresh routing table: {}", e);
-                            ttl
+                _ = sleep_until(refresh_at) => {
+                    for db in registry.due_for_refresh() {
+                        if let Err(e) = refresh_if_expired(&config, &registry, &provider, db.as_ref()).await {
+                            debug!("Failed to refresh routing table of {:?}: {}", db, e);
+                            registry.postpone_refresh(db.as_ref());
                         }
-                    };
-                    interval = tokio::time::interval(Duration::from_secs(ttl)); // recreate interval with the new TTL
+                    }
                 }
-                // Handle forced updates
+                // a routing table has been fetched, which may expire earlier
+                _ = registry.updated.notified() => {}
                 cmd = rx.recv() => {
                     match cmd {
-                        Some(RegistryCommand::Refresh(new_bookmarks)) => {
-                            *bookmarks.lock().await = new_bookmarks;
-                            ttl = match refresh_routing_table(config_clone.clone(), registry.clone(), provider.clone(), bookmarks.lock().await.as_slice()).await {
-                                Ok(ttl) => ttl,
-                                Err(e) => {
-                                    debug!("Failed to refresh routing table: {}", e);
-                                    ttl
-                                }
-                            };
-                            interval = tokio::time::interval(Duration::from_secs(ttl)); // recreate interval with the new TTL
-                        }
                         Some(RegistryCommand::Stop) | None => {
                             debug!("Stopping background updater");
                             break;
@@ -161,41 +469,157 @@ pub(crate) fn start_background_updater(
                     }
                 }
             }
-
-            interval.tick().await;
         }
     });
     tx
 }
 
+/// Sleeps until `deadline`, or forever without one.
+async fn sleep_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
 impl ConnectionRegistry {
     /// Retrieve the pool for a specific server.
     pub fn get_pool(&self, server: &BoltServer) -> Option<ConnectionPool> {
         self.connections.get(server).map(|entry| entry.clone())
     }
 
+    /// Removes the pool of `server` and removes it from the routing table of every database.
     pub fn mark_unavailable(&self, server: &BoltServer) {
         self.connections.remove(server);
+        for mut table in self.routing_tables.iter_mut() {
+            table.servers.retain(|s| s != server);
+        }
+    }
+
+    /// The servers in the routing table of `db`, with the number of connections in use.
+    pub fn servers(&self, db: Option<&Database>) -> Vec<BoltServer> {
+        let mut servers = self
+            .routing_tables
+            .get(&db.cloned())
+            .map(|table| table.servers.clone())
+            .unwrap_or_default();
+        for server in &mut servers {
+            if let Some(pool) = self.connections.get(server) {
+                let status = pool.status();
+                server.in_flight = status.size.saturating_sub(status.available);
+            }
+        }
+        servers
+    }
+
+    /// The routers in the routing table of `db`.
+    fn routers(&self, db: Option<&Database>) -> Vec<BoltServer> {
+        self.routing_tables
+            .get(&db.cloned())
+            .map(|table| {
+                table
+                    .servers
+                    .iter()
+                    .filter(|server| server.role == "ROUTE")
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The lock that a refresh of the routing table of `key` holds.
+    fn refresh_lock(&self, key: &RefreshKey) -> Arc<Mutex<()>> {
+        Arc::clone(&self.refreshing.entry(key.clone()).or_default())
+    }
+
+    /// The error of a refresh of the routing table of `key` that failed after `since`.
+    fn failed_since(&self, key: &RefreshKey, since: Instant) -> Option<Error> {
+        self.failures
+            .get(key)
+            .filter(|failure| failure.0 >= since)
+            .map(|failure| Error::RoutingTableRefreshFailed(failure.1.clone()))
+    }
+
+    /// Forces the routing table of `db` to be fetched again before its next use.
+    pub fn expire(&self, db: Option<&Database>) {
+        if let Some(mut table) = self.routing_tables.get_mut(&db.cloned()) {
+            table.expires_at = Instant::now();
+        }
+    }
+
+    /// Forgets the home databases of all users, e.g. because one of them no longer exists,
+    /// so that they are resolved again on their next use.
+    pub fn forget_home_dbs(&self) {
+        self.home_dbs.clear();
+    }
+
+    /// Removes all routing tables and pools.
+    pub fn clear(&self) {
+        self.routing_tables.clear();
+        self.failures.clear();
+        self.home_dbs.clear();
+        self.connections.clear();
+    }
+
+    fn cached_home_db(&self, home: &HomeDbKey) -> Option<Option<Database>> {
+        self.home_dbs
+            .get(home)
+            .filter(|home| home.expires_at > Instant::now())
+            .map(|home| home.db.clone())
+    }
+
+    fn is_expired(&self, db: Option<&Database>) -> bool {
+        self.routing_tables
+            .get(&db.cloned())
+            .map_or(true, |table| table.expires_at <= Instant::now())
+    }
+
+    fn next_refresh(&self) -> Option<Instant> {
+        self.routing_tables
+            .iter()
+            .map(|table| table.refresh_at)
+            .min()
     }
 
-    pub fn servers(&self) -> Vec<BoltServer> {
-        self.connections
+    fn due_for_refresh(&self) -> Vec<Option<Database>> {
+        let now = Instant::now();
+        self.routing_tables
             .iter()
-            .map(|entry| entry.key().clone())
+            .filter(|table| table.refresh_at <= now)
+            .map(|table| table.key().clone())
             .collect()
     }
+
+    fn postpone_refresh(&self, db: Option<&Database>) {
+        if let Some(mut table) = self.routing_tables.get_mut(&db.cloned()) {
+            table.refresh_at = Instant::now() + table.ttl;
+        }
+    }
+
+    /// Closes the pools of servers that are no longer in any routing table.
+    fn retain_routed_pools(&self) {
+        let routed = self
+            .routing_tables
+            .iter()
+            .flat_map(|table| table.servers.clone())
+            .collect::<HashSet<_>>();
+        self.connections.retain(|server, _| routed.contains(server));
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::auth::ConnectionTLSConfig;
+    use crate::auth::{AuthToken, ConnectionTLSConfig};
     use crate::routing::load_balancing::LoadBalancingStrategy;
-    use crate::routing::Server;
-    use crate::routing::{RoundRobinStrategy, RoutingTable};
+    use crate::routing::RoundRobinStrategy;
+    use crate::InMemoryBookmarkManager;
+    use std::collections::HashMap;
     use std::future::Future;
     use std::pin::Pin;
+    use std::sync::Mutex as StdMutex;
 
+    #[derive(Debug)]
     struct TestRoutingTableProvider {
         routing_table: RoutingTable,
     }
@@ -211,12 +635,380 @@ mod tests {
             &self,
             _: &Config,
             _bookmarks: &[String],
+            _imp_user: Option<&str>,
         ) -> Pin<Box<dyn Future<Output = Result<RoutingTable, Error>> + Send>> {
             let routing_table = self.routing_table.clone();
             Box::pin(async move { Ok(routing_table) })
         }
     }
 
+    /// Answers with a routing table whose only server is named after the database.
+    #[derive(Debug, Default)]
+    struct PerDatabaseProvider {
+        ttls: HashMap<Database, u64>,
+        fetched: StdMutex<Vec<Option<Database>>>,
+    }
+
+    impl PerDatabaseProvider {
+        fn with_ttl(mut self, db: &str, ttl: u64) -> Self {
+            self.ttls.insert(db.into(), ttl);
+            self
+        }
+
+        fn fetched(&self) -> Vec<Option<Database>> {
+            self.fetched.lock().unwrap().clone()
+        }
+    }
+
+    impl RoutingTableProvider for PerDatabaseProvider {
+        fn fetch_routing_table(
+            &self,
+            config: &Config,
+            _bookmarks: &[String],
+            _imp_user: Option<&str>,
+        ) -> Pin<Box<dyn Future<Output = Result<RoutingTable, Error>> + Send>> {
+            self.fetched.lock().unwrap().push(config.db.clone());
+            let host = config.db.as_deref().unwrap_or("home");
+            let routing_table = RoutingTable {
+                ttl: config
+                    .db
+                    .as_ref()
+                    .and_then(|db| self.ttls.get(db))
+                    .map_or(300, |ttl| *ttl),
+                db: config.db.clone(),
+                servers: vec![Server {
+                    addresses: vec![format!("{host}:7687")],
+                    role: "WRITE".to_string(),
+                }],
+            };
+            Box::pin(async move { Ok(routing_table) })
+        }
+    }
+
+    /// Answers with the routing tables of the routers in `answers`,
+    /// and fails for every other router.
+    #[derive(Debug, Default)]
+    struct RouterProvider {
+        answers: HashMap<String, Result<RoutingTable, &'static str>>,
+        delay: Duration,
+        asked: StdMutex<Vec<String>>,
+    }
+
+    impl RouterProvider {
+        fn answer(mut self, router: &str, answer: Result<RoutingTable, &'static str>) -> Self {
+            self.answers.insert(format!("neo4j://{router}"), answer);
+            self
+        }
+
+        fn asked(&self) -> Vec<String> {
+            self.asked.lock().unwrap().clone()
+        }
+    }
+
+    impl RoutingTableProvider for RouterProvider {
+        fn fetch_routing_table(
+            &self,
+            config: &Config,
+            _bookmarks: &[String],
+            _imp_user: Option<&str>,
+        ) -> Pin<Box<dyn Future<Output = Result<RoutingTable, Error>> + Send>> {
+            self.asked.lock().unwrap().push(config.uri.clone());
+            let answer = match self.answers.get(&config.uri) {
+                Some(Ok(routing_table)) => Ok(routing_table.clone()),
+                Some(Err(code)) => Err(Error::Neo4j(crate::Neo4jError::new(
+                    code.to_string(),
+                    "oh no".into(),
+                ))),
+                None => Err(Error::ServerUnavailableError(format!(
+                    "{} is down",
+                    config.uri
+                ))),
+            };
+            let delay = self.delay;
+            Box::pin(async move {
+                tokio::time::sleep(delay).await;
+                answer
+            })
+        }
+    }
+
+    /// The routing table of `neo4j` with a single writer and `routers`.
+    fn cluster<const N: usize>(routers: [&str; N]) -> RoutingTable {
+        RoutingTable::new(
+            Duration::from_secs(300),
+            [
+                Server::new("ROUTE", routers),
+                Server::new("WRITE", ["writer"]),
+            ],
+        )
+        .with_db("neo4j")
+    }
+
+    async fn refresh(
+        config: &Config,
+        registry: &Arc<ConnectionRegistry>,
+        provider: &Arc<RouterProvider>,
+    ) -> Result<(), Error> {
+        let provider: Arc<dyn RoutingTableProvider> = provider.clone();
+        let db = config.db.clone();
+        registry.expire(db.as_ref());
+        refresh_if_expired(config, registry, &provider, db.as_ref()).await
+    }
+
+    fn config() -> Config {
+        Config {
+            uri: "neo4j://localhost:7687".to_string(),
+            auth: Arc::new(AuthToken::basic("user", "password")),
+            max_connections: 10,
+            db: Some("neo4j".into()),
+            fetch_size: 0,
+            tls_config: ConnectionTLSConfig::None,
+            connection: Default::default(),
+            pool: Default::default(),
+            bookmark_manager: Arc::new(InMemoryBookmarkManager::new()),
+            routing_table_provider: Arc::new(PerDatabaseProvider::default()),
+            load_balancing_strategy: Arc::new(RoundRobinStrategy::default()),
+            address_resolver: None,
+            connect_address: None,
+        }
+    }
+
+    fn addresses(registry: &ConnectionRegistry, db: Option<&Database>) -> Vec<String> {
+        registry
+            .servers(db)
+            .into_iter()
+            .map(|server| server.address)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn keeps_a_routing_table_per_database() {
+        let provider = Arc::new(PerDatabaseProvider::default());
+        let dyn_provider: Arc<dyn RoutingTableProvider> = provider.clone();
+        let registry = Arc::new(ConnectionRegistry::default());
+        let (movies, people) = (Database::from("movies"), Database::from("people"));
+
+        for db in [&movies, &people, &movies] {
+            refresh_if_expired(&config(), &registry, &dyn_provider, Some(db))
+                .await
+                .unwrap();
+        }
+        assert_eq!(
+            provider.fetched(),
+            [Some(movies.clone()), Some(people.clone())]
+        );
+        assert_eq!(addresses(&registry, Some(&movies)), ["movies"]);
+        assert_eq!(addresses(&registry, Some(&people)), ["people"]);
+        assert!(addresses(&registry, None).is_empty());
+        assert_eq!(registry.connections.len(), 2);
+
+        // an expired routing table is fetched again, without affecting the other databases
+        registry.expire(Some(&movies));
+        refresh_if_expired(&config(), &registry, &dyn_provider, Some(&people))
+            .await
+            .unwrap();
+        refresh_if_expired(&config(), &registry, &dyn_provider, Some(&movies))
+            .await
+            .unwrap();
+        assert_eq!(provider.fetched().len(), 3);
+        assert_eq!(registry.connections.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn background_updater_refreshes_each_routing_table_after_its_ttl() {
+        let provider = Arc::new(PerDatabaseProvider::default().with_ttl("movies", 1));
+        let dyn_provider: Arc<dyn RoutingTableProvider> = provider.clone();
+        let registry = Arc::new(ConnectionRegistry::default());
+        let updater = start_background_updater(&config(), registry.clone(), dyn_provider.clone());
+        let (movies, people) = (Database::from("movies"), Database::from("people"));
+
+        for db in [&movies, &people] {
+            refresh_if_expired(&config(), &registry, &dyn_provider, Some(db))
+                .await
+                .unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        updater.send(RegistryCommand::Stop).await.unwrap();
+
+        assert_eq!(
+            provider.fetched(),
+            [Some(movies.clone()), Some(people), Some(movies)]
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_over_to_the_next_known_router() {
+        let provider = Arc::new(
+            RouterProvider::default()
+                .answer("localhost:7687", Ok(cluster(["router-1", "router-2"])))
+                .answer("router-2:7687", Ok(cluster(["router-2"]))),
+        );
+        let registry = Arc::new(ConnectionRegistry::default());
+
+        refresh(&config(), &registry, &provider).await.unwrap();
+        refresh(&config(), &registry, &provider).await.unwrap();
+        assert_eq!(
+            provider.asked(),
+            [
+                "neo4j://localhost:7687",
+                "neo4j://router-1:7687",
+                "neo4j://router-2:7687"
+            ]
+        );
+        let routers = registry.routers(Some(&"neo4j".into()));
+        assert_eq!(routers.len(), 1);
+        assert_eq!(routers[0].address, "router-2");
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_the_seed_router_once_all_known_routers_failed() {
+        let provider =
+            Arc::new(RouterProvider::default().answer("localhost:7687", Ok(cluster(["router-1"]))));
+        let registry = Arc::new(ConnectionRegistry::default());
+
+        refresh(&config(), &registry, &provider).await.unwrap();
+        refresh(&config(), &registry, &provider).await.unwrap();
+        assert_eq!(
+            provider.asked(),
+            [
+                "neo4j://localhost:7687",
+                "neo4j://router-1:7687",
+                "neo4j://localhost:7687"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn skips_a_router_that_sends_an_invalid_address() {
+        let invalid = RoutingTable::new(
+            Duration::from_secs(300),
+            [
+                Server::new("ROUTE", ["router-1"]),
+                Server::new("READ", ["reader:port"]),
+            ],
+        )
+        .with_db("neo4j");
+        let provider = Arc::new(
+            RouterProvider::default()
+                .answer("localhost:7687", Ok(cluster(["router-1"])))
+                .answer("router-1:7687", Ok(invalid)),
+        );
+        let registry = Arc::new(ConnectionRegistry::default());
+
+        refresh(&config(), &registry, &provider).await.unwrap();
+        refresh(&config(), &registry, &provider).await.unwrap();
+        assert_eq!(provider.asked().len(), 3);
+        assert_eq!(
+            addresses(&registry, Some(&"neo4j".into())),
+            ["router-1", "writer"]
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_with_the_errors_of_all_routers() {
+        let provider =
+            Arc::new(RouterProvider::default().answer("localhost:7687", Ok(cluster(["router-1"]))));
+        let registry = Arc::new(ConnectionRegistry::default());
+        refresh(&config(), &registry, &provider).await.unwrap();
+
+        let provider = Arc::new(RouterProvider::default());
+        let Err(Error::RoutingTableRefreshFailed(reason)) =
+            refresh(&config(), &registry, &provider).await
+        else {
+            panic!("expected the refresh to fail");
+        };
+        assert!(reason.contains("neo4j://router-1:7687 is down"), "{reason}");
+        assert!(
+            reason.contains("neo4j://localhost:7687 is down"),
+            "{reason}"
+        );
+    }
+
+    #[tokio::test]
+    async fn does_not_fail_over_when_the_database_does_not_exist() {
+        let provider = Arc::new(
+            RouterProvider::default()
+                .answer("localhost:7687", Ok(cluster(["router-1", "router-2"])))
+                .answer(
+                    "router-1:7687",
+                    Err("Neo.ClientError.Database.DatabaseNotFound"),
+                ),
+        );
+        let registry = Arc::new(ConnectionRegistry::default());
+
+        refresh(&config(), &registry, &provider).await.unwrap();
+        let error = refresh(&config(), &registry, &provider).await.unwrap_err();
+        assert!(error.is_fatal_discovery());
+        assert_eq!(provider.asked().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn fails_with_a_scheme_that_does_not_route() {
+        let provider = Arc::new(RouterProvider::default());
+        let config = Config {
+            uri: "bolt://localhost:7687".to_owned(),
+            ..config()
+        };
+        let registry = Arc::new(ConnectionRegistry::default());
+
+        let error = refresh(&config, &registry, &provider).await.unwrap_err();
+        assert!(matches!(error, Error::RoutingTableRefreshFailed(_)));
+        assert!(provider.asked().is_empty());
+    }
+
+    #[tokio::test]
+    async fn waiting_callers_fail_with_the_error_of_the_refresh_they_waited_for() {
+        let provider = Arc::new(RouterProvider {
+            delay: Duration::from_millis(100),
+            ..Default::default()
+        });
+        let (config, registry) = (config(), Arc::new(ConnectionRegistry::default()));
+
+        let (first, second) = tokio::join!(
+            refresh(&config, &registry, &provider),
+            refresh(&config, &registry, &provider),
+        );
+        assert!(matches!(first, Err(Error::RoutingTableRefreshFailed(_))));
+        assert!(matches!(second, Err(Error::RoutingTableRefreshFailed(_))));
+        assert_eq!(provider.asked().len(), 1);
+
+        // a later caller tries again
+        assert!(refresh(&config, &registry, &provider).await.is_err());
+        assert_eq!(provider.asked().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn refreshes_of_different_databases_do_not_wait_for_each_other() {
+        let delay = Duration::from_millis(200);
+        let provider = Arc::new(RouterProvider {
+            delay,
+            ..Default::default()
+        });
+        let registry = Arc::new(ConnectionRegistry::default());
+        let movies = Config {
+            db: Some("movies".into()),
+            ..config()
+        };
+
+        let started = Instant::now();
+        let config = config();
+        let (first, second) = tokio::join!(
+            refresh(&config, &registry, &provider),
+            refresh(&movies, &registry, &provider),
+        );
+        assert!(first.is_err() && second.is_err());
+        assert_eq!(provider.asked().len(), 2);
+        assert!(started.elapsed() < 2 * delay);
+
+        // the failure is not reported to callers that fetch for another user
+        let neo4j = Some(Database::from("neo4j"));
+        assert!(registry
+            .failed_since(&(neo4j.clone(), None), started)
+            .is_some());
+        let alice = Some("alice".to_owned());
+        assert!(registry.failed_since(&(neo4j, alice), started).is_none());
+    }
+
     #[tokio::test]
     async fn test_available_servers() {
         let readers = vec![
@@ -253,38 +1045,34 @@ mod tests {
                 .chain(routers.clone())
                 .collect(),
         };
-        let config = Config {
-            uri: "neo4j://localhost:7687".to_string(),
-            user: "user".to_string(),
-            password: "password".to_string(),
-            max_connections: 10,
-            db: Some("neo4j".into()),
-            fetch_size: 0,
-            tls_config: ConnectionTLSConfig::None,
-        };
+        let config = config();
         let registry = Arc::new(ConnectionRegistry::default());
-        let ttl = refresh_routing_table(
+        let routing_table = refresh_routing_table(
             config.clone(),
             registry.clone(),
             Arc::new(TestRoutingTableProvider::new(cluster_routing_table)),
             &[],
+            None,
+            None,
         )
         .await
         .unwrap();
-        assert_eq!(ttl, 300);
+        assert_eq!(routing_table.ttl, 300);
         assert_eq!(registry.connections.len(), 5);
         let strategy = RoundRobinStrategy::default();
-        registry.mark_unavailable(BoltServer::resolve(&writers[0]).first().unwrap());
+        registry.mark_unavailable(BoltServer::resolve(&writers[0]).unwrap().first().unwrap());
         assert_eq!(registry.connections.len(), 4);
-        let writer = strategy.select_writer(&registry.servers()).unwrap();
+        let writer = strategy
+            .select_writer(&registry.servers(config.db.as_ref()))
+            .unwrap();
         assert_eq!(
             format!("{}:{}", writer.address, writer.port),
             writers[1].addresses[0]
         );
 
-        registry.mark_unavailable(BoltServer::resolve(&writers[1]).first().unwrap());
+        registry.mark_unavailable(BoltServer::resolve(&writers[1]).unwrap().first().unwrap());
         assert_eq!(registry.connections.len(), 3);
-        let writer = strategy.select_writer(&registry.servers());
+        let writer = strategy.select_writer(&registry.servers(config.db.as_ref()));
         assert!(writer.is_none());
     }
 }
@@ -0,0 +1,89 @@
+use crate::routing::connection_registry::BoltServer;
+use crate::routing::load_balancing::LoadBalancingStrategy;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A [`LoadBalancingStrategy`] that selects the server with the fewest connections in use,
+/// so that slow servers get fewer queries than with a [`crate::RoundRobinStrategy`].
+///
+/// Servers with the same number of connections in use are selected in turn.
+///
+/// # Examples
+///
+/// ```no_run
+/// use neo4rs::{ConfigBuilder, LeastInFlightStrategy};
+///
+/// let config = ConfigBuilder::default()
+///     .uri("neo4j://localhost:7687")
+///     .user("neo4j")
+///     .password("neo")
+///     .load_balancing_strategy(LeastInFlightStrategy::default())
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct LeastInFlightStrategy {
+    reader_index: AtomicUsize,
+    writer_index: AtomicUsize,
+}
+
+impl LeastInFlightStrategy {
+    fn select(servers: &[BoltServer], role: &str, index: &AtomicUsize) -> Option<BoltServer> {
+        let servers = servers
+            .iter()
+            .filter(|s| s.role == role)
+            .collect::<Vec<_>>();
+        let least = servers.iter().map(|s| s.in_flight).min()?;
+        let candidates = servers
+            .into_iter()
+            .filter(|s| s.in_flight == least)
+            .collect::<Vec<_>>();
+        let i = index.fetch_add(1, Ordering::Relaxed) % candidates.len();
+        Some(candidates[i].clone())
+    }
+}
+
+impl LoadBalancingStrategy for LeastInFlightStrategy {
+    fn select_reader(&self, servers: &[BoltServer]) -> Option<BoltServer> {
+        Self::select(servers, "READ", &self.reader_index)
+    }
+
+    fn select_writer(&self, servers: &[BoltServer]) -> Option<BoltServer> {
+        Self::select(servers, "WRITE", &self.writer_index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(address: &str, role: &str, in_flight: usize) -> BoltServer {
+        BoltServer {
+            address: address.to_string(),
+            port: 7687,
+            role: role.to_string(),
+            in_flight,
+        }
+    }
+
+    #[test]
+    fn selects_the_server_with_the_fewest_connections_in_use() {
+        let servers = [
+            server("reader-1", "READ", 3),
+            server("reader-2", "READ", 1),
+            server("reader-3", "READ", 1),
+            server("writer-1", "WRITE", 5),
+            server("writer-2", "WRITE", 2),
+        ];
+        let strategy = LeastInFlightStrategy::default();
+
+        let readers = (0..4)
+            .map(|_| strategy.select_reader(&servers).unwrap().address)
+            .collect::<Vec<_>>();
+        assert_eq!(readers, ["reader-2", "reader-3", "reader-2", "reader-3"]);
+        let writer = strategy.select_writer(&servers).unwrap();
+        assert_eq!(writer.address, "writer-2");
+
+        assert_eq!(strategy.select_reader(&servers[3..]), None);
+        assert_eq!(strategy.select_writer(&[]), None);
+    }
+}
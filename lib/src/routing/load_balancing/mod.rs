@@ -1,8 +1,23 @@
+pub(crate) mod least_in_flight_strategy;
 pub(crate) mod round_robin_strategy;
 
 use crate::routing::connection_registry::BoltServer;
+use std::fmt::Debug;
 
-pub trait LoadBalancingStrategy: Sync + Send {
+/// Selects the server of a routing table that a connection is acquired from.
+///
+/// The servers are the ones in the routing table of the database of a query,
+/// with the number of connections that are currently in use for each of them.
+/// The strategy is shared by a [`crate::Graph`] and all of its clones.
+///
+/// The default is a [`RoundRobinStrategy`], set another one with
+/// [`crate::ConfigBuilder::load_balancing_strategy`].
+///
+/// [`RoundRobinStrategy`]: crate::RoundRobinStrategy
+pub trait LoadBalancingStrategy: Debug + Send + Sync {
+    /// Selects one of the `READ` servers for a read query, `None` if there is none.
     fn select_reader(&self, servers: &[BoltServer]) -> Option<BoltServer>;
+
+    /// Selects one of the `WRITE` servers for a write query, `None` if there is none.
     fn select_writer(&self, servers: &[BoltServer]) -> Option<BoltServer>;
 }
@@ -2,7 +2,10 @@ use crate::routing::connection_registry::BoltServer;
 use crate::routing::load_balancing::LoadBalancingStrategy;
 use std::sync::atomic::AtomicUsize;
 
-#[derive(Default)]
+/// A [`LoadBalancingStrategy`] that selects the servers of a role in turn.
+///
+/// This is the default strategy.
+#[derive(Debug, Default)]
 pub struct RoundRobinStrategy {
     reader_index: AtomicUsize,
     writer_index: AtomicUsize,
@@ -84,7 +87,7 @@ mod tests {
                 .chain(writers.clone())
                 .collect(),
         };
-        let all_servers = cluster_routing_table.resolve();
+        let all_servers = cluster_routing_table.resolve().unwrap();
         assert_eq!(all_servers.len(), 4);
         let strategy = RoundRobinStrategy::default();
 
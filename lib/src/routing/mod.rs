@@ -2,8 +2,10 @@ mod connection_registry;
 mod load_balancing;
 mod routed_connection_manager;
 mod routing_table_provider;
+mod server_address_resolver;
 
 use std::fmt::{Display, Formatter};
+use std::time::Duration;
 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
 use {crate::connection::Routing, serde::Deserialize};
 
@@ -31,6 +33,7 @@ pub struct Extra {
     pub(crate) imp_user: Option<String>,
 }
 
+/// The servers of a cluster that serve a database, see [`RoutingTableProvider`].
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "unstable-bolt-protocol-impl-v2", derive(Deserialize))]
 pub struct RoutingTable {
@@ -40,14 +43,36 @@ pub struct RoutingTable {
 }
 
 impl RoutingTable {
-    pub(crate) fn resolve(&self) -> Vec<BoltServer> {
-        self.servers
+    /// Creates a routing table of the home database that is valid for `ttl`,
+    /// which is rounded down to whole seconds.
+    pub fn new(ttl: Duration, servers: impl IntoIterator<Item = Server>) -> Self {
+        Self {
+            ttl: ttl.as_secs(),
+            db: None,
+            servers: servers.into_iter().collect(),
+        }
+    }
+
+    /// The database that the routing table belongs to.
+    pub fn with_db(self, db: impl Into<Database>) -> Self {
+        Self {
+            db: Some(db.into()),
+            ..self
+        }
+    }
+
+    /// The servers at the addresses of all entries, which fails if an address cannot be parsed.
+    pub(crate) fn resolve(&self) -> Result<Vec<BoltServer>, crate::Error> {
+        let servers = self
+            .servers
             .iter()
-            .flat_map(BoltServer::resolve)
-            .collect::<Vec<BoltServer>>()
+            .map(BoltServer::resolve)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(servers.into_iter().flatten().collect())
     }
 }
 
+/// An entry of a [`RoutingTable`], the addresses of the servers with a role.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "unstable-bolt-protocol-impl-v2", derive(Deserialize))]
 pub struct Server {
@@ -55,6 +80,20 @@ pub struct Server {
     pub(crate) role: String, // TODO: use an enum here
 }
 
+impl Server {
+    /// Creates an entry of a routing table for the servers at `addresses`, e.g. `localhost:7687`,
+    /// with the role `ROUTE`, `READ` or `WRITE`.
+    pub fn new<A: Into<String>>(
+        role: impl Into<String>,
+        addresses: impl IntoIterator<Item = A>,
+    ) -> Self {
+        Self {
+            addresses: addresses.into_iter().map(Into::into).collect(),
+            role: role.into(),
+        }
+    }
+}
+
 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
 pub struct RouteBuilder<'a> {
     routing: Routing,
@@ -81,7 +120,6 @@ impl<'a> RouteBuilder<'a> {
         }
     }
 
-    #[allow(dead_code)]
     pub fn with_imp_user(self, imp_user: &'a str) -> Self {
         Self {
             imp_user: Some(imp_user),
@@ -161,8 +199,12 @@ impl Display for RoutingTable {
     }
 }
 
-use crate::routing::connection_registry::BoltServer;
 use crate::{Database, Version};
+pub use connection_registry::BoltServer;
+pub use load_balancing::least_in_flight_strategy::LeastInFlightStrategy;
 pub use load_balancing::round_robin_strategy::RoundRobinStrategy;
+pub use load_balancing::LoadBalancingStrategy;
 pub use routed_connection_manager::RoutedConnectionManager;
-pub use routing_table_provider::ClusterRoutingTableProvider;
+pub use routing_table_provider::{ClusterRoutingTableProvider, RoutingTableProvider};
+pub(crate) use server_address_resolver::seed_routers;
+pub use server_address_resolver::{ServerAddressResolver, ServerAddressResolverFn};
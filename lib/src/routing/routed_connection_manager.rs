@@ -1,99 +1,181 @@
-use crate::pool::ManagedConnection;
+use crate::auth::{AuthToken, AuthTokenManager};
+use crate::connection::{Connection, ConnectionInfo, ServerInfo};
+use crate::metrics::PoolMetrics;
+use crate::pool::{acquire, ManagedConnection};
 use crate::routing::connection_registry::{
-    start_background_updater, BoltServer, ConnectionRegistry, RegistryCommand,
+    refresh_if_expired, resolve_home_db, start_background_updater, BoltServer, ConnectionRegistry,
+    RegistryCommand,
 };
 use crate::routing::load_balancing::LoadBalancingStrategy;
 use crate::routing::routing_table_provider::RoutingTableProvider;
-use crate::routing::RoundRobinStrategy;
+use crate::routing::seed_routers;
 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
-use crate::{Config, Error, Operation};
+use crate::{Config, Database, Error, Operation};
 use backon::ExponentialBuilder;
-use futures::lock::Mutex;
 use log::{debug, error};
-use std::{sync::Arc, time::Duration};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::Duration;
 use tokio::sync::mpsc::Sender;
 
 #[derive(Clone)]
 pub struct RoutedConnectionManager {
     load_balancing_strategy: Arc<dyn LoadBalancingStrategy>,
     connection_registry: Arc<ConnectionRegistry>,
-    bookmarks: Arc<Mutex<Vec<String>>>,
     backoff: ExponentialBuilder,
     channel: Sender<RegistryCommand>,
+    config: Arc<Config>,
+    provider: Arc<dyn RoutingTableProvider>,
+    closed: Arc<AtomicBool>,
 }
 
 impl RoutedConnectionManager {
-    pub fn new(config: &Config, provider: Arc<dyn RoutingTableProvider>) -> Result<Self, Error> {
+    /// Creates a manager that routes with the routing table provider and
+    /// load balancing strategy of `config`.
+    pub fn new(config: &Config) -> Result<Self, Error> {
         let backoff = crate::pool::backoff();
         let connection_registry = Arc::new(ConnectionRegistry::default());
-        let channel = start_background_updater(config, connection_registry.clone(), provider);
+        let provider = Arc::clone(&config.routing_table_provider);
+        let channel =
+            start_background_updater(config, connection_registry.clone(), provider.clone());
         Ok(RoutedConnectionManager {
-            load_balancing_strategy: Arc::new(RoundRobinStrategy::default()),
-            bookmarks: Arc::new(Mutex::new(vec![])),
+            load_balancing_strategy: Arc::clone(&config.load_balancing_strategy),
             connection_registry,
             backoff,
             channel,
+            config: Arc::new(config.clone()),
+            provider,
+            closed: Arc::new(AtomicBool::new(false)),
         })
     }
 
+    /// Resolves the home database of the user of `auth`, or the configured one, or of `imp_user`,
+    /// with a `ROUTE` request on behalf of that user, which is cached until its routing table expires.
+    pub(crate) async fn home_db(
+        &self,
+        auth: Option<&Arc<AuthToken>>,
+        imp_user: Option<&str>,
+    ) -> Result<Option<Database>, Error> {
+        if self.closed.load(Ordering::Acquire) {
+            return Err(Error::GraphClosed);
+        }
+        resolve_home_db(
+            &self.config,
+            &self.connection_registry,
+            &self.provider,
+            auth,
+            imp_user,
+        )
+        .await
+    }
+
+    /// Forgets the cached home databases after an error that means that a database does not exist,
+    /// e.g. because the home database of a user has been changed or dropped.
+    pub(crate) fn handle_error(&self, error: &Error) {
+        if error.is_fatal_discovery() {
+            debug!("Forgetting the cached home databases after: {error}");
+            self.connection_registry.forget_home_dbs();
+        }
+    }
+
+    /// Connects to the seed router to verify that it can be reached with the credentials of `auth`,
+    /// or with the configured ones, trying every router that the seed uri resolves to.
+    pub(crate) async fn server_info(&self, auth: Option<&AuthToken>) -> Result<ServerInfo, Error> {
+        if self.closed.load(Ordering::Acquire) {
+            return Err(Error::GraphClosed);
+        }
+        let auth = match auth {
+            Some(auth) => Arc::new(auth.clone()),
+            None => self.config.auth.clone(),
+        };
+        let mut last_error = None;
+        for router in seed_routers(&self.config).await? {
+            match Self::router_info(&router, auth.clone()).await {
+                Ok(server_info) => return Ok(server_info),
+                Err(e) => {
+                    debug!(
+                        "Failed to connect to router {:?}: {}",
+                        router.connect_address, e
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error
+            .unwrap_or_else(|| Error::ServerUnavailableError("No router to connect to".to_owned())))
+    }
+
+    async fn router_info(
+        router: &Config,
+        auth: Arc<dyn AuthTokenManager>,
+    ) -> Result<ServerInfo, Error> {
+        let info = ConnectionInfo::for_router(router, auth)?;
+        let connection = Connection::new(&info).await?;
+        let server_info = connection.server_info(&router.uri);
+        if let Err(e) = connection.goodbye().await {
+            debug!("Failed to close connection to router: {}", e);
+        }
+        Ok(server_info)
+    }
+
+    /// Returns a connection to a server of `db` that can handle `operation`,
+    /// fetching the routing table of `db` first if it has not been used yet or has expired.
     pub(crate) async fn get(
         &self,
+        db: Option<&Database>,
         operation: Option<Operation>,
     ) -> Result<ManagedConnection, Error> {
         let op = operation.unwrap_or(Operation::Write);
-        loop {
-            // we loop here until we get a connection. If the routing table is empty, we force a refresh
-            if self.connection_registry.connections.is_empty() {
-                // the first time we need to wait until we get the routing table
-                tokio::time::sleep(Duration::from_millis(10)).await;
-                continue;
-            }
+        if self.closed.load(Ordering::Acquire) {
+            return Err(Error::GraphClosed);
+        }
+        refresh_if_expired(&self.config, &self.connection_registry, &self.provider, db).await?;
 
-            while let Some(server) = match op {
-                Operation::Write => self.select_writer(),
-                _ => self.select_reader(),
-            } {
-                debug!("requesting connection for server: {:?}", server);
-                if let Some(pool) = self.connection_registry.get_pool(&server) {
-                    match pool.get().await {
-                        Ok(connection) => return Ok(connection),
-                        Err(e) => {
-                            error!(
-                                "Failed to get connection from pool for server `{}`: {}",
-                                server.address, e
-                            );
-                            self.connection_registry.mark_unavailable(&server);
-                            continue;
-                        }
+        let mut last_error = None;
+        while let Some(server) = match op {
+            Operation::Write => self.select_writer(db),
+            _ => self.select_reader(db),
+        } {
+            debug!("requesting connection for server: {:?}", server);
+            if let Some(pool) = self.connection_registry.get_pool(&server) {
+                match acquire(&pool).await {
+                    Ok(connection) => return Ok(connection),
+                    // the server is busy, not unavailable
+                    Err(e @ Error::ConnectionAcquisitionTimeout(_)) => return Err(e),
+                    // the server answered, e.g. that the credentials have expired
+                    Err(e @ Error::Neo4j(_)) => return Err(e),
+                    Err(e) => {
+                        error!(
+                            "Failed to get connection from pool for server `{}`: {}",
+                            server.address, e
+                        );
+                        self.connection_registry.mark_unavailable(&server);
+                        last_error = Some(e);
+                        continue;
                     }
-                } else {
-                    // We couldn't find a connection manager for the server, it was probably marked unavailable
-                    error!(
-                        "No connection manager available for router `{}` in the registry",
-                        server.address
-                    );
-                    return Err(Error::ServerUnavailableError(format!(
-                        "No connection manager available for router `{}` in the registry",
-                        server.address
-                    )));
                 }
+            } else {
+                // We couldn't find a connection manager for the server, it was probably marked unavailable
+                error!(
+                    "No connection manager available for router `{}` in the registry",
+                    server.address
+                );
+                return Err(Error::ServerUnavailableError(format!(
+                    "No connection manager available for router `{}` in the registry",
+                    server.address
+                )));
             }
-            debug!("Routing table is empty for requested {op} operation, forcing refresh");
-            self.channel
-                .send(RegistryCommand::Refresh(
-                    self.bookmarks.lock().await.clone(),
-                ))
-                .await
-                .map_err(|e| {
-                    error!("Failed to send refresh command to registry: {}", e);
-                    Error::RoutingTableRefreshFailed(
-                        "Failed to send refresh command to registry".to_string(),
-                    )
-                })?;
-            // table is not empty, but we couldn't get a connection, so we throw an error
-            break Err(Error::ServerUnavailableError(format!(
+        }
+        debug!("Routing table of {db:?} is empty for requested {op} operation, forcing refresh");
+        self.connection_registry.expire(db);
+        match last_error {
+            // e.g. a connection timeout, which is retried with the refreshed routing table
+            Some(e) if e.can_retry() => Err(e),
+            _ => Err(Error::ServerUnavailableError(format!(
                 "No server available for {op} operation"
-            )));
+            ))),
         }
     }
 
@@ -101,26 +183,355 @@ impl RoutedConnectionManager {
         self.backoff
     }
 
-    fn select_reader(&self) -> Option<BoltServer> {
+    pub(crate) fn auth(&self) -> &dyn AuthTokenManager {
+        &*self.config.auth
+    }
+
+    /// Stops the background updater of the routing table and closes the pools of all servers,
+    /// see [`crate::pool::close`].
+    pub(crate) async fn close(&self, timeout: Option<Duration>) {
+        self.closed.store(true, Ordering::Release);
+        if let Err(e) = self.channel.send(RegistryCommand::Stop).await {
+            debug!("Background updater already stopped: {}", e);
+        }
+        // wait for the updater to exit, so that it does not create new pools
+        self.channel.closed().await;
+        let pools = self
+            .connection_registry
+            .connections
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect::<Vec<_>>();
+        futures::future::join_all(pools.iter().map(|pool| crate::pool::close(pool, timeout))).await;
+        self.connection_registry.clear();
+    }
+
+    /// The metrics of the pools of all servers in the routing table.
+    pub(crate) fn metrics(&self) -> Vec<PoolMetrics> {
+        self.connection_registry
+            .connections
+            .iter()
+            .map(|entry| crate::pool::metrics(entry.value()))
+            .collect()
+    }
+
+    fn select_reader(&self, db: Option<&Database>) -> Option<BoltServer> {
         self.load_balancing_strategy
-            .select_reader(&self.connection_registry.servers())
+            .select_reader(&self.connection_registry.servers(db))
     }
 
-    fn select_writer(&self) -> Option<BoltServer> {
+    fn select_writer(&self, db: Option<&Database>) -> Option<BoltServer> {
         self.load_balancing_strategy
-            .select_writer(&self.connection_registry.servers())
+            .select_writer(&self.connection_registry.servers(db))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{AuthToken, ConnectionTLSConfig};
+    use crate::config::ConnectionConfig;
+    use crate::routing::RoundRobinStrategy;
+    use crate::routing::{RoutingTable, Server};
+    use crate::test_server::{TestServer, GOODBYE, HELLO};
+    use crate::version::Version;
+    use crate::InMemoryBookmarkManager;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::Mutex as StdMutex;
+
+    /// Answers with the home database of the impersonated user, if any,
+    /// or else of the user that authenticated with other credentials than the configured ones.
+    #[derive(Debug, Default)]
+    struct HomeDbProvider {
+        requests: StdMutex<Vec<(Option<Database>, Option<String>)>>,
+    }
+
+    impl RoutingTableProvider for HomeDbProvider {
+        fn fetch_routing_table(
+            &self,
+            config: &Config,
+            _bookmarks: &[String],
+            imp_user: Option<&str>,
+        ) -> Pin<Box<dyn Future<Output = Result<RoutingTable, Error>> + Send>> {
+            let imp_user = imp_user.map(str::to_owned);
+            self.requests
+                .lock()
+                .unwrap()
+                .push((config.db.clone(), imp_user.clone()));
+            let auth = Arc::clone(&config.auth);
+            Box::pin(async move {
+                let token = auth.get_token().await?;
+                let user = imp_user.or(token.principal().map(str::to_owned));
+                Ok(RoutingTable {
+                    ttl: 300,
+                    db: Some(match user.as_deref() {
+                        None | Some("user") => "neo4j".into(),
+                        Some(user) => format!("{user}-db").into(),
+                    }),
+                    servers: vec![],
+                })
+            })
+        }
+    }
+
+    /// Routes every database to its own server.
+    #[derive(Debug)]
+    struct PerDatabaseProvider {
+        servers: Vec<(Database, String)>,
+    }
+
+    impl RoutingTableProvider for PerDatabaseProvider {
+        fn fetch_routing_table(
+            &self,
+            config: &Config,
+            _bookmarks: &[String],
+            _imp_user: Option<&str>,
+        ) -> Pin<Box<dyn Future<Output = Result<RoutingTable, Error>> + Send>> {
+            let servers = self
+                .servers
+                .iter()
+                .filter(|(db, _)| Some(db) == config.db.as_ref())
+                .flat_map(|(_, address)| {
+                    ["READ", "WRITE"].map(|role| Server {
+                        addresses: vec![address.clone()],
+                        role: role.to_string(),
+                    })
+                })
+                .collect();
+            let routing_table = RoutingTable {
+                ttl: 300,
+                db: config.db.clone(),
+                servers,
+            };
+            Box::pin(async move { Ok(routing_table) })
+        }
     }
 
-    #[allow(dead_code)]
-    pub(crate) async fn add_bookmark(&self, bookmark: &str) {
-        let mut guard = self.bookmarks.lock().await;
-        if !guard.contains(&bookmark.to_string()) {
-            guard.push(bookmark.to_string());
+    fn config() -> Config {
+        Config {
+            uri: "neo4j://localhost:7687".to_string(),
+            auth: Arc::new(AuthToken::basic("user", "password")),
+            max_connections: 10,
+            db: Some("neo4j".into()),
+            fetch_size: 0,
+            tls_config: ConnectionTLSConfig::None,
+            connection: Default::default(),
+            pool: Default::default(),
+            bookmark_manager: Arc::new(InMemoryBookmarkManager::new()),
+            routing_table_provider: Arc::new(HomeDbProvider::default()),
+            load_balancing_strategy: Arc::new(RoundRobinStrategy::default()),
+            address_resolver: None,
+            connect_address: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn resolves_home_db_of_imp_user() {
+        let provider = Arc::new(HomeDbProvider::default());
+        let manager = RoutedConnectionManager::new(&Config {
+            routing_table_provider: provider.clone(),
+            ..config()
+        })
+        .unwrap();
+
+        let db = manager.home_db(None, Some("alice")).await.unwrap();
+        assert_eq!(db, Some("alice-db".into()));
+
+        let requests = provider.requests.lock().unwrap().clone();
+        assert!(requests.contains(&(None, Some("alice".to_owned()))));
+    }
+
+    #[tokio::test]
+    async fn caches_the_home_db_of_every_user() {
+        let provider = Arc::new(HomeDbProvider::default());
+        let manager = RoutedConnectionManager::new(&Config {
+            routing_table_provider: provider.clone(),
+            ..config()
+        })
+        .unwrap();
+
+        for _ in 0..2 {
+            assert_eq!(
+                manager.home_db(None, None).await.unwrap(),
+                Some("neo4j".into())
+            );
+            assert_eq!(
+                manager.home_db(None, Some("alice")).await.unwrap(),
+                Some("alice-db".into())
+            );
         }
+
+        let requests = provider.requests.lock().unwrap().clone();
+        assert_eq!(requests, [(None, None), (None, Some("alice".to_owned()))]);
     }
 
-    #[allow(dead_code)]
-    pub(crate) async fn clear_bookmarks(&self) {
-        self.bookmarks.lock().await.clear();
+    #[tokio::test]
+    async fn caches_the_home_db_of_every_authenticated_user() {
+        let provider = Arc::new(HomeDbProvider::default());
+        let manager = RoutedConnectionManager::new(&Config {
+            routing_table_provider: provider.clone(),
+            ..config()
+        })
+        .unwrap();
+        let bob = Arc::new(AuthToken::basic("bob", "secret"));
+
+        for _ in 0..2 {
+            assert_eq!(
+                manager.home_db(Some(&bob), None).await.unwrap(),
+                Some("bob-db".into())
+            );
+            assert_eq!(
+                manager.home_db(None, None).await.unwrap(),
+                Some("neo4j".into())
+            );
+        }
+        assert_eq!(provider.requests.lock().unwrap().len(), 2);
+
+        // without a principal, the home database is resolved every time
+        let bearer = Arc::new(AuthToken::bearer("token"));
+        for _ in 0..2 {
+            manager.home_db(Some(&bearer), None).await.unwrap();
+        }
+        assert_eq!(provider.requests.lock().unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn forgets_the_home_db_when_a_database_is_not_found() {
+        let provider = Arc::new(HomeDbProvider::default());
+        let manager = RoutedConnectionManager::new(&Config {
+            routing_table_provider: provider.clone(),
+            ..config()
+        })
+        .unwrap();
+
+        manager.home_db(None, None).await.unwrap();
+        manager.handle_error(&Error::Neo4j(crate::Neo4jError::new(
+            "Neo.ClientError.Statement.SyntaxError".into(),
+            "oh no".into(),
+        )));
+        manager.home_db(None, None).await.unwrap();
+        assert_eq!(provider.requests.lock().unwrap().len(), 1);
+
+        manager.handle_error(&Error::Neo4j(crate::Neo4jError::new(
+            "Neo.ClientError.Database.DatabaseNotFound".into(),
+            "oh no".into(),
+        )));
+        manager.home_db(None, None).await.unwrap();
+        assert_eq!(provider.requests.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn close_stops_the_background_updater() {
+        let provider = Arc::new(HomeDbProvider::default());
+        let manager = RoutedConnectionManager::new(&Config {
+            routing_table_provider: provider,
+            ..config()
+        })
+        .unwrap();
+
+        manager.close(None).await;
+        assert!(manager.channel.is_closed());
+        assert!(matches!(
+            manager.get(None, Some(Operation::Read)).await,
+            Err(Error::GraphClosed)
+        ));
+        assert!(matches!(
+            manager.home_db(None, Some("alice")).await,
+            Err(Error::GraphClosed)
+        ));
+    }
+
+    #[tokio::test]
+    async fn server_info_asks_the_seed_router() {
+        let server = TestServer::start(Version::V5_0, |_| None).await;
+        let config = Config {
+            uri: format!("neo4j://{}", server.addr),
+            ..config()
+        };
+        let manager = RoutedConnectionManager::new(&config).unwrap();
+
+        let info = manager.server_info(None).await.unwrap();
+        assert_eq!(info.address, config.uri);
+        assert_eq!(info.agent, "Neo4j/5.0.0");
+        assert_eq!(info.protocol_version, Version::V5_0);
+
+        server.wait_for(GOODBYE).await;
+        let hello = &server.requests()[0];
+        assert_eq!(hello.signature, HELLO);
+        assert!(hello.map(0).value.contains_key("routing"));
+    }
+
+    #[tokio::test]
+    async fn routes_each_database_with_its_own_routing_table() {
+        let movies_server = TestServer::start(Version::V4_4, |_| None).await;
+        let people_server = TestServer::start(Version::V4_4, |_| None).await;
+        let (movies, people) = (Database::from("movies"), Database::from("people"));
+        let provider = PerDatabaseProvider {
+            servers: vec![
+                (movies.clone(), movies_server.addr.to_string()),
+                (people.clone(), people_server.addr.to_string()),
+            ],
+        };
+        let manager = RoutedConnectionManager::new(&Config {
+            routing_table_provider: Arc::new(provider),
+            ..config()
+        })
+        .unwrap();
+
+        drop(
+            manager
+                .get(Some(&movies), Some(Operation::Write))
+                .await
+                .unwrap(),
+        );
+        drop(
+            manager
+                .get(Some(&people), Some(Operation::Read))
+                .await
+                .unwrap(),
+        );
+
+        for server in [&movies_server, &people_server] {
+            let requests = server.requests();
+            assert_eq!(requests.len(), 1);
+            assert_eq!(requests[0].signature, HELLO);
+        }
+        assert_eq!(manager.metrics().len(), 4);
+
+        // the default database has no servers in this cluster
+        assert!(matches!(
+            manager.get(None, Some(Operation::Write)).await,
+            Err(Error::ServerUnavailableError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn connection_timeouts_can_be_retried() {
+        // never answers, like a server that is overloaded
+        let server = TestServer::start(Version::V4_4, |_| Some(Vec::new())).await;
+        let movies = Database::from("movies");
+        let provider = PerDatabaseProvider {
+            servers: vec![(movies.clone(), server.addr.to_string())],
+        };
+        let manager = RoutedConnectionManager::new(&Config {
+            routing_table_provider: Arc::new(provider),
+            connection: ConnectionConfig {
+                hello_timeout: Some(Duration::from_millis(50)),
+                ..Default::default()
+            },
+            ..config()
+        })
+        .unwrap();
+
+        let error = manager
+            .get(Some(&movies), Some(Operation::Write))
+            .await
+            .err()
+            .unwrap();
+        assert!(
+            matches!(error, Error::ConnectionTimeout(..)) && error.can_retry(),
+            "{error}"
+        );
     }
 }
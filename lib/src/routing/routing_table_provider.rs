@@ -1,17 +1,68 @@
 use crate::connection::{Connection, ConnectionInfo};
 use crate::routing::{RouteBuilder, RoutingTable};
 use crate::{Config, Error};
+use std::fmt::Debug;
 use std::future::Future;
 use std::pin::Pin;
 
-pub(crate) trait RoutingTableProvider: Send + Sync {
+/// Fetches the routing table of a database, which lists the servers of a cluster and their roles.
+///
+/// The provider is asked for the routing table of a database the first time it is used,
+/// and again whenever it has expired after its TTL.
+/// [`Config::db`] is the database whose routing table is requested,
+/// `None` for the home database of the user, or of `imp_user` if it is set.
+///
+/// The default is a [`ClusterRoutingTableProvider`], set another one with
+/// [`crate::ConfigBuilder::routing_table_provider`], e.g. a static routing table for tests.
+///
+/// # Examples
+///
+/// ```no_run
+/// use neo4rs::{Config, ConfigBuilder, Error, RoutingTable, RoutingTableProvider, Server};
+/// use std::{future::Future, pin::Pin, time::Duration};
+///
+/// #[derive(Debug)]
+/// struct SingleServer;
+///
+/// impl RoutingTableProvider for SingleServer {
+///     fn fetch_routing_table(
+///         &self,
+///         config: &Config,
+///         _bookmarks: &[String],
+///         _imp_user: Option<&str>,
+///     ) -> Pin<Box<dyn Future<Output = Result<RoutingTable, Error>> + Send>> {
+///         let servers = ["ROUTE", "READ", "WRITE"].map(|role| Server::new(role, ["localhost:7687"]));
+///         let mut routing_table = RoutingTable::new(Duration::from_secs(300), servers);
+///         if let Some(db) = config.db() {
+///             routing_table = routing_table.with_db(db.clone());
+///         }
+///         Box::pin(async move { Ok(routing_table) })
+///     }
+/// }
+///
+/// let config = ConfigBuilder::default()
+///     .uri("neo4j://localhost:7687")
+///     .user("neo4j")
+///     .password("neo")
+///     .routing_table_provider(SingleServer)
+///     .build()
+///     .unwrap();
+/// ```
+pub trait RoutingTableProvider: Debug + Send + Sync {
+    /// Fetches the routing table of [`Config::db`], waiting for `bookmarks`,
+    /// on behalf of `imp_user` if it is set.
     fn fetch_routing_table(
         &self,
         config: &Config,
         bookmarks: &[String],
+        imp_user: Option<&str>,
     ) -> Pin<Box<dyn Future<Output = Result<RoutingTable, Error>> + Send>>;
 }
 
+/// A [`RoutingTableProvider`] that asks the server at [`Config`]'s uri with a `ROUTE` request.
+///
+/// This is the default provider.
+#[derive(Debug, Default)]
 pub struct ClusterRoutingTableProvider;
 
 impl RoutingTableProvider for ClusterRoutingTableProvider {
@@ -19,21 +70,21 @@ impl RoutingTableProvider for ClusterRoutingTableProvider {
         &self,
         config: &Config,
         bookmarks: &[String],
+        imp_user: Option<&str>,
     ) -> Pin<Box<dyn Future<Output = Result<RoutingTable, Error>> + Send>> {
         let config = config.clone();
         let bookmarks = bookmarks.to_vec();
+        let imp_user = imp_user.map(str::to_owned);
         Box::pin(async move {
-            let info = ConnectionInfo::new(
-                &config.uri,
-                &config.user,
-                &config.password,
-                &config.tls_config,
-            )?;
+            let info = ConnectionInfo::for_router(&config, config.auth.clone())?;
             let mut connection = Connection::new(&info).await?;
             let mut builder = RouteBuilder::new(info.init.routing, bookmarks);
             if let Some(db) = config.db.clone() {
                 builder = builder.with_db(db);
             }
+            if let Some(imp_user) = imp_user.as_deref() {
+                builder = builder.with_imp_user(imp_user);
+            }
             connection.route(builder.build(connection.version())).await
         })
     }
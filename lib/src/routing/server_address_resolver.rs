@@ -0,0 +1,211 @@
+use crate::connection::NeoUrl;
+use crate::{Config, Error};
+use futures::future::BoxFuture;
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+
+/// Resolves the address of the seed router, the one in the uri of the [`Config`],
+/// into the addresses of the routers that are asked for routing tables.
+///
+/// Use this when the seed address is not a host name that the operating system can resolve,
+/// e.g. a virtual name of a service discovery that stands for several routers.
+/// The routers are tried in turn until one of them answers.
+/// The seed address is resolved again every time that a routing table is fetched from it,
+/// so that changes of the service discovery are picked up.
+///
+/// The addresses in the routing tables are not resolved,
+/// they are expected to be reachable as reported by the cluster.
+///
+/// See [`ServerAddressResolverFn`] to resolve addresses with a function.
+pub trait ServerAddressResolver: Debug + Send + Sync {
+    /// Resolves `address`, e.g. `cluster.example.com:7687`, into one or more addresses,
+    /// e.g. `10.0.0.1:7687`, which are connected to with the scheme of the seed uri.
+    fn resolve<'a>(&'a self, address: &'a str) -> BoxFuture<'a, Result<Vec<String>, Error>>;
+}
+
+type ResolveFn = dyn Fn(&str) -> BoxFuture<'static, Result<Vec<String>, Error>> + Send + Sync;
+
+/// A [`ServerAddressResolver`] that resolves addresses with a function.
+///
+/// # Examples
+///
+/// ```no_run
+/// use neo4rs::{ConfigBuilder, ServerAddressResolverFn};
+///
+/// // every router of the cluster behind `cluster.internal:7687`
+/// let resolver = ServerAddressResolverFn::new(|_address| {
+///     vec!["10.0.0.1:7687", "10.0.0.2:7687", "10.0.0.3:7687"]
+/// });
+/// let config = ConfigBuilder::default()
+///     .uri("neo4j://cluster.internal:7687")
+///     .user("neo4j")
+///     .password("neo")
+///     .address_resolver(resolver)
+///     .build()
+///     .unwrap();
+/// ```
+pub struct ServerAddressResolverFn {
+    resolve: Box<ResolveFn>,
+}
+
+impl ServerAddressResolverFn {
+    /// Creates a resolver that maps an address to the addresses returned by `resolve`.
+    pub fn new<F, A>(resolve: F) -> Self
+    where
+        F: Fn(&str) -> Vec<A> + Send + Sync + 'static,
+        A: Into<String>,
+    {
+        Self {
+            resolve: Box::new(move |address| {
+                let addresses = resolve(address).into_iter().map(Into::into).collect();
+                Box::pin(async move { Ok(addresses) })
+            }),
+        }
+    }
+
+    /// Creates a resolver that maps an address to the addresses returned by the future of `resolve`,
+    /// e.g. by asking a service discovery.
+    pub fn new_async<F, Fut>(resolve: F) -> Self
+    where
+        F: Fn(String) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<Vec<String>, Error>> + Send + 'static,
+    {
+        Self {
+            resolve: Box::new(move |address| Box::pin(resolve(address.to_owned()))),
+        }
+    }
+}
+
+impl ServerAddressResolver for ServerAddressResolverFn {
+    fn resolve<'a>(&'a self, address: &'a str) -> BoxFuture<'a, Result<Vec<String>, Error>> {
+        (self.resolve)(address)
+    }
+}
+
+impl Debug for ServerAddressResolverFn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServerAddressResolverFn")
+            .finish_non_exhaustive()
+    }
+}
+
+/// The routers that the seed uri of `config` resolves to, which keep the seed uri
+/// to verify their certificates and connect to their resolved address instead,
+/// or just the seed router without a [`ServerAddressResolver`].
+pub(crate) async fn seed_routers(config: &Config) -> Result<Vec<Config>, Error> {
+    let Some(resolver) = &config.address_resolver else {
+        return Ok(vec![config.clone()]);
+    };
+    let url = NeoUrl::parse(&config.uri)?;
+    let address = format!("{}:{}", url.host(), url.port());
+    let routers = resolver
+        .resolve(&address)
+        .await?
+        .into_iter()
+        .map(|resolved| Config {
+            connect_address: Some(resolved),
+            ..config.clone()
+        })
+        .collect::<Vec<_>>();
+    if routers.is_empty() {
+        return Err(Error::RoutingTableRefreshFailed(format!(
+            "The address resolver returned no addresses for `{address}`"
+        )));
+    }
+    Ok(routers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::{ConnectionInfo, Routing};
+    use crate::ConfigBuilder;
+    use rustls::pki_types::ServerName;
+    use url::Host;
+
+    fn config(resolver: Option<ServerAddressResolverFn>) -> Config {
+        let builder = ConfigBuilder::default()
+            .uri("neo4j+s://cluster.internal")
+            .user("neo4j")
+            .password("neo");
+        match resolver {
+            Some(resolver) => builder.address_resolver(resolver),
+            None => builder,
+        }
+        .build()
+        .unwrap()
+    }
+
+    async fn resolve(config: &Config) -> Vec<(String, Option<String>)> {
+        seed_routers(config)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|router| (router.uri, router.connect_address))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn resolves_the_seed_router() {
+        let seed = "neo4j+s://cluster.internal".to_owned();
+        assert_eq!(resolve(&config(None)).await, [(seed.clone(), None)]);
+
+        let resolver = ServerAddressResolverFn::new(|address| {
+            assert_eq!(address, "cluster.internal:7687");
+            vec!["10.0.0.1:7687", "10.0.0.2:7688"]
+        });
+        assert_eq!(
+            resolve(&config(Some(resolver))).await,
+            [
+                (seed.clone(), Some("10.0.0.1:7687".to_owned())),
+                (seed.clone(), Some("10.0.0.2:7688".to_owned()))
+            ]
+        );
+
+        let resolver = ServerAddressResolverFn::new_async(|address| async move {
+            Ok(vec![address.replace("cluster", "router")])
+        });
+        assert_eq!(
+            resolve(&config(Some(resolver))).await,
+            [(seed, Some("router.internal:7687".to_owned()))]
+        );
+    }
+
+    #[tokio::test]
+    async fn verifies_the_certificate_of_a_resolved_router_against_the_seed_host() {
+        // the tests link more than one crypto provider
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let resolver = ServerAddressResolverFn::new(|_| vec!["10.0.0.1:7688"]);
+        let routers = seed_routers(&config(Some(resolver))).await.unwrap();
+        let auth = routers[0].auth.clone();
+        let info = ConnectionInfo::for_router(&routers[0], auth).unwrap();
+
+        assert_eq!(info.prepare.host, Host::Domain("10.0.0.1".into()));
+        assert_eq!(info.prepare.port, 7688);
+        let (_, server_name) = info.prepare.encryption.unwrap();
+        assert_eq!(
+            server_name,
+            ServerName::try_from("cluster.internal").unwrap()
+        );
+        let Routing::Yes(context) = info.init.routing else {
+            panic!("expected a routing context");
+        };
+        assert_eq!(
+            context.to_vec(),
+            [("address".into(), "cluster.internal:7687".into())]
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_without_resolved_addresses() {
+        let resolver = ServerAddressResolverFn::new(|_| Vec::<String>::new());
+        let error = seed_routers(&config(Some(resolver))).await.unwrap_err();
+        assert!(matches!(error, Error::RoutingTableRefreshFailed(_)));
+
+        let resolver = ServerAddressResolverFn::new_async(|_| async {
+            Err(Error::ServerUnavailableError("discovery is down".into()))
+        });
+        let error = seed_routers(&config(Some(resolver))).await.unwrap_err();
+        assert!(matches!(error, Error::ServerUnavailableError(_)));
+    }
+}
@@ -151,7 +151,7 @@ impl Point3D {
 impl Row {
     pub fn new(fields: BoltList, data: BoltList) -> Self {
         let mut attributes = BoltMap::with_capacity(fields.len());
-        for (field, value) in fields.into_iter().zip(data.into_iter()) {
+        for (field, value) in fields.into_iter().zip(data) {
             if let Ok(key) = field.try_into() {
                 attributes.put(key, value);
             }
@@ -204,6 +204,14 @@ impl Node {
         self.inner.id.value
     }
 
+    /// Element id of the node, only sent by servers that speak Bolt 5.0 or newer.
+    ///
+    /// Unlike [`Node::id`], the element id is stable and can be used to
+    /// reference the node across transactions.
+    pub fn element_id(&self) -> Option<&str> {
+        self.inner.element_id()
+    }
+
     /// various labels attached to this node
     pub fn labels(&self) -> Vec<&str> {
         self.to::<crate::Labels<_>>().unwrap().0
@@ -248,6 +256,21 @@ impl Relation {
         self.inner.end_node_id.value
     }
 
+    /// Element id of the relationship, only sent by servers that speak Bolt 5.0 or newer.
+    pub fn element_id(&self) -> Option<&str> {
+        self.inner.element_id()
+    }
+
+    /// Element id of the start node, only sent by servers that speak Bolt 5.0 or newer.
+    pub fn start_node_element_id(&self) -> Option<&str> {
+        self.inner.start_node_element_id()
+    }
+
+    /// Element id of the end node, only sent by servers that speak Bolt 5.0 or newer.
+    pub fn end_node_element_id(&self) -> Option<&str> {
+        self.inner.end_node_element_id()
+    }
+
     pub fn typ(&self) -> &str {
         self.to::<crate::Type<_>>().unwrap().0
     }
@@ -283,6 +306,11 @@ impl UnboundedRelation {
         self.inner.id.value
     }
 
+    /// Element id of the relationship, only sent by servers that speak Bolt 5.0 or newer.
+    pub fn element_id(&self) -> Option<&str> {
+        self.inner.element_id()
+    }
+
     pub fn typ(&self) -> &str {
         self.to::<crate::Type<_>>().unwrap().0
     }
@@ -0,0 +1,286 @@
+use crate::{
+    bookmarks::{BookmarkManager, SessionBookmarks},
+    summary::ResultSummary,
+    Database, Graph, Operation, Query, Result, Txn,
+};
+use futures::future::BoxFuture;
+use std::sync::Arc;
+
+/// The configuration of a [`Session`], see [`Graph::session`].
+#[derive(Debug, Clone)]
+pub struct SessionConfig {
+    pub(crate) db: Option<Database>,
+    pub(crate) operation: Operation,
+    pub(crate) imp_user: Option<String>,
+    pub(crate) bookmarks: Vec<String>,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            db: None,
+            operation: Operation::Write,
+            imp_user: None,
+            bookmarks: Vec::new(),
+        }
+    }
+}
+
+impl SessionConfig {
+    /// A session on the database of the graph, in write mode and without bookmarks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The database of the session, instead of the one of the graph.
+    pub fn with_db(mut self, db: impl Into<Database>) -> Self {
+        self.db = Some(db.into());
+        self
+    }
+
+    /// The access mode of [`Session::start_txn`] and [`Session::run`], defaults to [`Operation::Write`].
+    pub fn with_operation(mut self, operation: Operation) -> Self {
+        self.operation = operation;
+        self
+    }
+
+    /// Runs all work of the session on behalf of `user`, see [`Graph::with_imp_user`].
+    pub fn with_imp_user(mut self, user: impl Into<String>) -> Self {
+        self.imp_user = Some(user.into());
+        self
+    }
+
+    /// Bookmarks that the first transaction of the session waits for,
+    /// e.g. the [`Session::last_bookmarks`] of another session.
+    pub fn with_bookmarks<B: Into<String>>(
+        mut self,
+        bookmarks: impl IntoIterator<Item = B>,
+    ) -> Self {
+        self.bookmarks = bookmarks.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// A causally chained sequence of transactions.
+///
+/// Every transaction of a session starts with the bookmark of the previously committed one,
+/// so that it sees all writes of the session, even when it runs on a different cluster member.
+/// Sessions are cheap to create and are not meant to be shared between unrelated units of work.
+///
+/// # Examples
+///
+/// ```no_run
+/// use neo4rs::{query, Graph, SessionConfig};
+///
+/// # async fn run(graph: Graph) -> neo4rs::Result<()> {
+/// let session = graph.session(SessionConfig::new().with_db("movies"));
+/// session.run("CREATE (:Movie {title: 'Heat'})").await?;
+/// // this read sees the movie that was created above
+/// let count = session
+///     .read_transaction(|txn| {
+///         Box::pin(async move {
+///             let mut rows = txn.execute("MATCH (m:Movie) RETURN count(m) AS count").await?;
+///             let row = rows.next(txn.handle()).await?.expect("one row");
+///             Ok(row.get::<i64>("count")?)
+///         })
+///     })
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Session {
+    graph: Graph,
+    operation: Operation,
+    bookmarks: Arc<SessionBookmarks>,
+}
+
+impl Session {
+    pub(crate) fn new(graph: Graph, operation: Operation, bookmarks: Vec<String>) -> Self {
+        // committed transactions also advance the bookmarks of the graph
+        let manager = Arc::clone(&graph.config.bookmark_manager);
+        Self {
+            graph,
+            operation,
+            bookmarks: Arc::new(SessionBookmarks::new(bookmarks, Some(manager))),
+        }
+    }
+
+    /// Starts a new transaction with the access mode of the session.
+    ///
+    /// The bookmarks of the session advance once the transaction is committed.
+    /// Transactions will not be automatically retried on any failure.
+    pub async fn start_txn(&self) -> Result<Txn> {
+        self.graph
+            .impl_start_txn_on(
+                self.graph.config.db.clone(),
+                self.operation,
+                &self.bookmark_manager(),
+            )
+            .await
+    }
+
+    /// Runs a query in an auto-commit transaction with the access mode of the session,
+    /// retrying it in the same way as [`Graph::run`].
+    pub async fn run(&self, q: impl Into<Query>) -> Result<ResultSummary> {
+        self.graph
+            .impl_run_on(
+                self.graph.config.db.clone(),
+                q.into(),
+                self.operation,
+                &self.bookmark_manager(),
+            )
+            .await
+    }
+
+    /// Runs `work` in a write transaction of this session, see [`Graph::write_transaction`].
+    pub async fn write_transaction<F, R>(&self, work: F) -> Result<R>
+    where
+        F: FnMut(&mut Txn) -> BoxFuture<'_, Result<R>>,
+    {
+        self.graph
+            .impl_execute_txn(Operation::Write, &self.bookmark_manager(), work)
+            .await
+    }
+
+    /// Runs `work` in a read transaction of this session, see [`Graph::read_transaction`].
+    pub async fn read_transaction<F, R>(&self, work: F) -> Result<R>
+    where
+        F: FnMut(&mut Txn) -> BoxFuture<'_, Result<R>>,
+    {
+        self.graph
+            .impl_execute_txn(Operation::Read, &self.bookmark_manager(), work)
+            .await
+    }
+
+    /// The bookmarks of the last committed transaction of this session,
+    /// or the initial bookmarks if nothing has been committed yet.
+    ///
+    /// Pass them to [`SessionConfig::with_bookmarks`] to chain another session after this one.
+    pub fn last_bookmarks(&self) -> Vec<String> {
+        self.bookmarks.get()
+    }
+
+    fn bookmark_manager(&self) -> Arc<dyn BookmarkManager> {
+        Arc::clone(&self.bookmarks) as Arc<dyn BookmarkManager>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_server::{self, TestServer, BEGIN, COMMIT, DISCARD, RUN},
+        version::Version,
+        ConfigBuilder,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn chains_bookmarks_across_transactions() {
+        let commits = AtomicUsize::new(0);
+        let server = TestServer::start(Version::V4_4, move |request| {
+            matches!(request.signature, COMMIT | DISCARD).then(|| {
+                let bookmark = format!("bm-{}", commits.fetch_add(1, Ordering::SeqCst) + 1);
+                vec![test_server::success([("bookmark", bookmark.into())])]
+            })
+        })
+        .await;
+        let config = ConfigBuilder::default()
+            .uri(server.uri())
+            .user("neo4j")
+            .password("neo4j")
+            .build()
+            .unwrap();
+        let graph = Graph::connect(config).unwrap();
+
+        let session = graph.session(
+            SessionConfig::new()
+                .with_db("movies")
+                .with_operation(Operation::Read)
+                .with_bookmarks(["bm-0"]),
+        );
+        assert_eq!(session.last_bookmarks(), ["bm-0"]);
+
+        session.run("RETURN 1").await.unwrap();
+        assert_eq!(session.last_bookmarks(), ["bm-1"]);
+
+        let txn = session.start_txn().await.unwrap();
+        txn.commit().await.unwrap();
+        assert_eq!(session.last_bookmarks(), ["bm-2"]);
+
+        session
+            .write_transaction(|_| Box::pin(async { Ok(()) }))
+            .await
+            .unwrap();
+        assert_eq!(session.last_bookmarks(), ["bm-3"]);
+
+        // a new session does not inherit the bookmarks of another one
+        graph
+            .session(SessionConfig::new())
+            .start_txn()
+            .await
+            .unwrap();
+
+        let requests = server
+            .requests()
+            .into_iter()
+            .filter(|request| matches!(request.signature, RUN | BEGIN))
+            .filter(|request| {
+                request
+                    .map(request.fields.len() - 1)
+                    .value
+                    .contains_key("db")
+            })
+            .collect::<Vec<_>>();
+        let sent = requests
+            .iter()
+            .map(|request| {
+                let extra = request.map(request.fields.len() - 1);
+                (
+                    request.signature,
+                    extra.get::<String>("mode").unwrap(),
+                    extra.get::<Vec<String>>("bookmarks").unwrap_or_default(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let bookmarks = |bookmark: &str| vec![bookmark.to_owned()];
+        assert_eq!(
+            sent,
+            [
+                (RUN, "r".to_owned(), bookmarks("bm-0")),
+                (BEGIN, "r".to_owned(), bookmarks("bm-1")),
+                (BEGIN, "w".to_owned(), bookmarks("bm-2")),
+            ]
+        );
+        let requests = server.requests();
+        let begin = requests.iter().rfind(|r| r.signature == BEGIN).unwrap();
+        let bookmarks = begin.map(0).get::<Vec<String>>("bookmarks").unwrap();
+        assert!(bookmarks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn commits_of_a_session_are_visible_to_the_graph() {
+        let server = TestServer::start(Version::V4_4, |request| {
+            (request.signature == COMMIT)
+                .then(|| vec![test_server::success([("bookmark", "bm-1".into())])])
+        })
+        .await;
+        let config = ConfigBuilder::default()
+            .uri(server.uri())
+            .user("neo4j")
+            .password("neo4j")
+            .build()
+            .unwrap();
+        let graph = Graph::connect(config).unwrap();
+
+        let session = graph.session(SessionConfig::new());
+        session.start_txn().await.unwrap().commit().await.unwrap();
+        graph.run("RETURN 1").await.unwrap();
+
+        let requests = server.requests();
+        let run = requests.iter().rfind(|r| r.signature == RUN).unwrap();
+        let bookmarks = run.map(2).get::<Vec<String>>("bookmarks").unwrap();
+        assert_eq!(bookmarks, ["bm-1"]);
+    }
+}
@@ -16,10 +16,18 @@ use crate::{
     DeError, RunResult,
 };
 
-use futures::{stream::try_unfold, TryStream};
+use log::debug;
+
+use futures::{future::BoxFuture, stream::try_unfold, Stream, TryStream};
 use serde::de::DeserializeOwned;
 
-use std::collections::VecDeque;
+use std::{
+    collections::VecDeque,
+    future::poll_fn,
+    marker::PhantomData,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
 
 #[cfg(feature = "unstable-result-summary")]
 type BoxedSummary = Box<ResultSummary>;
@@ -63,15 +71,62 @@ impl RowStream {
 ///
 /// A stream will contain a connection from the connection pool which will be released to the pool
 /// when the stream is dropped.
+/// If the server has more records at that point, they are discarded in the background first,
+/// so that the connection is returned in a known state.
+///
+/// The stream is also a [`futures::Stream`] of `T`, which is a [`crate::Row`] unless the stream
+/// has been converted with [`DetachedRowStream::into_typed`].
+/// Unlike [`DetachedRowStream::into_stream`], it does not borrow anything,
+/// so that it can be returned from a function, e.g. as a streaming response.
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::TryStreamExt as _;
+/// use neo4rs::{query, DetachedRowStream, Graph};
+///
+/// # async fn run(graph: Graph) -> neo4rs::Result<()> {
+/// async fn names(graph: &Graph) -> neo4rs::Result<DetachedRowStream<String>> {
+///     let rows = graph.execute(query("MATCH (p:Person) RETURN p.name AS name")).await?;
+///     Ok(rows.into_typed())
+/// }
+///
+/// let names = names(&graph).await?.try_collect::<Vec<_>>().await?;
+/// # Ok(())
+/// # }
+/// ```
 #[must_use = "Results must be streamed through with `next` in order to execute the query"]
-pub struct DetachedRowStream {
+pub struct DetachedRowStream<T = Row> {
+    /// Taken by a fetch that was started by polling this stream, until the fetch completes.
+    inner: Option<Detached>,
+    pending: Option<BoxFuture<'static, (Detached, Result<Option<Row>>)>>,
+    convert: fn(Row) -> Result<T, DeError>,
+    _row: PhantomData<fn() -> T>,
+}
+
+struct Detached {
     stream: RowStream,
     connection: ManagedConnection,
 }
 
+impl Detached {
+    /// Discards the remaining records, or closes the connection if that fails.
+    async fn release(mut self) {
+        if let Err(e) = self.stream.discard(&mut self.connection).await {
+            debug!("Failed to discard the remaining records, closing the connection: {e}");
+            drop(ManagedConnection::take(self.connection));
+        }
+    }
+}
+
 impl DetachedRowStream {
     pub(crate) fn new(stream: RowStream, connection: ManagedConnection) -> Self {
-        DetachedRowStream { stream, connection }
+        DetachedRowStream {
+            inner: Some(Detached { stream, connection }),
+            pending: None,
+            convert: Ok,
+            _row: PhantomData,
+        }
     }
 }
 
@@ -245,55 +300,65 @@ impl RowStream {
 
     /// Stop consuming the stream and return a summary, if available.
     /// Stopping the stream will also discard any messages on the server side.
-    pub async fn finish(mut self, mut handle: impl TransactionHandle) -> Result<RunResult> {
-        self.buffer.clear();
+    pub async fn finish(mut self, handle: impl TransactionHandle) -> Result<RunResult> {
+        self.discard(handle).await?;
 
         #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
         match self.state {
-            State::Ready => {
-                let summary = {
-                    let connected = handle.connection();
-                    connected
-                        .send_recv_as(Discard::all().for_query(self.qid))
-                        .await
-                }?;
-                let summary = match summary {
-                    Summary::Success(s) => match s.metadata {
-                        Streaming::Done(summary) => *summary,
-                        Streaming::HasMore => {
-                            unreachable!("Query returned has_more after a discard_all");
-                        }
-                    },
-                    Summary::Ignored => {
-                        return Err(Error::RequestIgnoredError);
+            State::Complete(summary) => Ok(*summary),
+            State::Ready => unreachable!("the stream is complete after a discard"),
+        }
+
+        #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
+        Ok(())
+    }
+
+    /// Stop consuming the stream, and tell the server to discard the remaining records
+    /// of this query with a `DISCARD` message.
+    ///
+    /// Buffered records are dropped as well, and the stream returns no more rows afterwards.
+    /// Nothing is sent if the server has no more records.
+    pub async fn discard(&mut self, mut handle: impl TransactionHandle) -> Result<()> {
+        self.buffer.clear();
+        if self.state != State::Ready {
+            return Ok(());
+        }
+
+        #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+        {
+            let summary = handle
+                .connection()
+                .send_recv_as(Discard::all().for_query(self.qid))
+                .await?;
+            match summary {
+                Summary::Success(s) => match s.metadata {
+                    Streaming::Done(summary) => {
+                        self.state = State::Complete(summary);
+                        Ok(())
                     }
-                    Summary::Failure(f) => {
-                        return Err(f.into_error());
+                    Streaming::HasMore => {
+                        unreachable!("Query returned has_more after a discard_all");
                     }
-                };
-                Ok(summary)
+                },
+                Summary::Ignored => Err(Error::RequestIgnoredError),
+                Summary::Failure(f) => Err(f.into_error()),
             }
-            State::Complete(summary) => Ok(*summary),
         }
 
         #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
-        match self.state {
-            State::Ready => {
-                let summary = {
-                    let connected = handle.connection();
-                    connected
-                        .send_recv(BoltRequest::discard_all_for(self.qid))
-                        .await
-                }?;
-                let summary = match summary {
-                    crate::messages::BoltResponse::Success(_) => Ok(()),
-                    crate::messages::BoltResponse::Failure(f) => Err(Error::Neo4j(f.into_error())),
-                    msg => Err(msg.into_error("DISCARD")),
-                };
-                self.state = State::Complete(());
-                summary
+        {
+            let summary = handle
+                .connection()
+                .send_recv(BoltRequest::discard_all_for(self.qid))
+                .await?;
+            match summary {
+                BoltResponse::Success(_) => {
+                    self.state = State::Complete(());
+                    Ok(())
+                }
+                BoltResponse::Failure(f) => Err(Error::Neo4j(f.into_error())),
+                msg => Err(msg.into_error("DISCARD")),
             }
-            State::Complete(_) => Ok(()),
         }
     }
 
@@ -354,20 +419,55 @@ impl RowStream {
     }
 }
 
-impl DetachedRowStream {
+impl<T> DetachedRowStream<T> {
+    /// Converts this stream into a [`futures::Stream`] where
+    /// every row is converted into a `U` by calling [`crate::row::Row::to`].
+    pub fn into_typed<U: DeserializeOwned>(mut self) -> DetachedRowStream<U> {
+        DetachedRowStream {
+            inner: self.inner.take(),
+            pending: self.pending.take(),
+            convert: |row| row.to::<U>(),
+            _row: PhantomData,
+        }
+    }
+
+    /// Waits for a fetch that was started by polling this as a [`futures::Stream`],
+    /// and returns the stream with its connection.
+    async fn resume(&mut self) -> Result<&mut Detached> {
+        if let Some(fetch) = self.pending.as_mut() {
+            // the fetch is only taken once it completed, so that it survives a cancelled call
+            let (mut inner, row) = poll_fn(|cx| fetch.as_mut().poll(cx)).await;
+            self.pending = None;
+            let row = match row {
+                Ok(row) => row,
+                Err(e) => {
+                    self.inner = Some(inner);
+                    return Err(e);
+                }
+            };
+            if let Some(row) = row {
+                inner.stream.buffer.push_front(row);
+            }
+            self.inner = Some(inner);
+        }
+        self.inner.as_mut().ok_or(Error::ConnectionError)
+    }
+
     /// A call to next() will return a row from an internal buffer if the buffer has any entries,
     /// if the buffer is empty and the server has more rows left to consume, then a new batch of rows
     /// are fetched from the server (using the fetch_size value configured see [`crate::ConfigBuilder::fetch_size`])
     pub async fn next(&mut self) -> Result<Option<Row>> {
-        self.stream.next(&mut self.connection).await
+        let inner = self.resume().await?;
+        inner.stream.next(&mut inner.connection).await
     }
 
     /// Return the [`RowStream::next`] item,
     /// converted into a `T` by calling [`crate::row::Row::to`].
     ///
     /// Unlike `next`, this method returns a missing items as an error ([`Error::NoMoreRows`]).
-    pub async fn next_as<'this, T: DeserializeOwned + 'this>(&'this mut self) -> Result<T> {
-        self.stream.next_as(&mut self.connection).await
+    pub async fn next_as<'this, U: DeserializeOwned + 'this>(&'this mut self) -> Result<U> {
+        let inner = self.resume().await?;
+        inner.stream.next_as(&mut inner.connection).await
     }
 
     /// Return the first [`crate::Row`] in the result.
@@ -375,7 +475,8 @@ impl DetachedRowStream {
     /// If there are 0 results, [`Error::NoMoreRows`] is returned.
     /// If there are 2 or more results, [`Error::NotSingleResult`] is returned.
     pub async fn single(&mut self) -> Result<Row> {
-        self.stream.single(&mut self.connection).await
+        let inner = self.resume().await?;
+        inner.stream.single(&mut inner.connection).await
     }
 
     /// Return the first [`crate::Row`] in the result.
@@ -383,8 +484,9 @@ impl DetachedRowStream {
     ///
     /// If there are 0 results, [`Error::NoMoreRows`] is returned.
     /// If there are 2 or more results, [`Error::NotSingleResult`] is returned.
-    pub async fn single_as<'this, T: DeserializeOwned + 'this>(&'this mut self) -> Result<T> {
-        self.stream.single_as(&mut self.connection).await
+    pub async fn single_as<'this, U: DeserializeOwned + 'this>(&'this mut self) -> Result<U> {
+        let inner = self.resume().await?;
+        inner.stream.single_as(&mut inner.connection).await
     }
 
     /// Return the first [`crate::Row`] buffered result without consuming it.
@@ -395,7 +497,7 @@ impl DetachedRowStream {
     /// As such, returning `None` does not mean that there are no more results,
     /// it just means that the buffer is empty.
     pub fn peek(&self) -> Option<&Row> {
-        self.stream.peek()
+        self.inner.as_ref()?.stream.peek()
     }
 
     /// Return the first [`crate::Row`] buffered result without consuming it,
@@ -406,8 +508,11 @@ impl DetachedRowStream {
     ///
     /// As such, returning [`Error::NoMoreRows`] does not mean that there are no more results,
     /// it just means that the buffer is empty.
-    pub fn peek_as<'this, T: DeserializeOwned + 'this>(&'this self) -> Result<T> {
-        self.stream.peek_as()
+    pub fn peek_as<'this, U: DeserializeOwned + 'this>(&'this self) -> Result<U> {
+        self.inner
+            .as_ref()
+            .ok_or(Error::NoMoreRows)
+            .and_then(|inner| inner.stream.peek_as())
     }
 
     /// Return the first [`crate::Row`] buffered result and consume it.
@@ -418,7 +523,7 @@ impl DetachedRowStream {
     /// As such, returning `None` does not mean that there are no more results,
     /// it just means that the buffer is empty.
     pub fn pop(&mut self) -> Option<Row> {
-        self.stream.pop()
+        self.inner.as_mut()?.stream.pop()
     }
 
     /// Return the first [`crate::Row`] buffered result and consume it,
@@ -429,14 +534,31 @@ impl DetachedRowStream {
     ///
     /// As such, returning [`Error::NoMoreRows`] does not mean that there are no more results,
     /// it just means that the buffer is empty.
-    pub fn pop_as<'this, T: DeserializeOwned + 'this>(&'this mut self) -> Result<T> {
-        self.stream.pop_as()
+    pub fn pop_as<'this, U: DeserializeOwned + 'this>(&'this mut self) -> Result<U> {
+        self.inner
+            .as_mut()
+            .ok_or(Error::NoMoreRows)
+            .and_then(|inner| inner.stream.pop_as())
     }
 
     /// Stop consuming the stream and return a summary, if available.
     /// Stopping the stream will also discard any messages on the server side.
     pub async fn finish(mut self) -> Result<RunResult> {
-        self.stream.finish(&mut self.connection).await
+        self.resume().await?;
+        let Detached {
+            stream,
+            mut connection,
+        } = self.inner.take().ok_or(Error::ConnectionError)?;
+        stream.finish(&mut connection).await
+    }
+
+    /// Stop consuming the stream, and tell the server to discard the remaining records.
+    ///
+    /// This happens in the background when the stream is dropped early,
+    /// calling it explicitly reports any failure and returns the connection right away.
+    pub async fn discard(&mut self) -> Result<()> {
+        let inner = self.resume().await?;
+        inner.stream.discard(&mut inner.connection).await
     }
 
     /// Turns this RowStream into a [`futures::stream::TryStream`] where
@@ -446,7 +568,7 @@ impl DetachedRowStream {
     /// After the returned stream is consumed, this stream can be [`Self::finish`]ed to get the summary.
     #[allow(clippy::wrong_self_convention)]
     pub fn into_stream(&mut self) -> impl TryStream<Ok = Row, Error = Error> + '_ {
-        self.stream.into_stream(&mut self.connection)
+        self.convert_rows(Ok)
     }
 
     /// Turns this RowStream into a [`futures::stream::TryStream`] where
@@ -455,10 +577,10 @@ impl DetachedRowStream {
     /// The stream can only be converted once.
     /// After the returned stream is consumed, this stream can be [`Self::finish`]ed to get the summary.
     #[allow(clippy::wrong_self_convention)]
-    pub fn into_stream_as<'this, T: DeserializeOwned + 'this>(
+    pub fn into_stream_as<'this, U: DeserializeOwned + 'this>(
         &'this mut self,
-    ) -> impl TryStream<Ok = T, Error = Error> + 'this {
-        self.stream.into_stream_as(&mut self.connection)
+    ) -> impl TryStream<Ok = U, Error = Error> + 'this {
+        self.convert_rows(|row| row.to::<U>())
     }
 
     /// Turns this RowStream into a [`futures::stream::TryStream`] where
@@ -467,11 +589,83 @@ impl DetachedRowStream {
     ///
     /// The stream can only be converted once.
     /// After the returned stream is consumed, this stream can be [`Self::finish`]ed to get the summary.
-    pub fn column_into_stream<'this, 'db: 'this, T: DeserializeOwned + 'db>(
+    pub fn column_into_stream<'this, 'db: 'this, U: DeserializeOwned + 'db>(
         &'this mut self,
         column: &'db str,
-    ) -> impl TryStream<Ok = T, Error = Error> + 'this {
-        self.stream.column_into_stream(&mut self.connection, column)
+    ) -> impl TryStream<Ok = U, Error = Error> + 'this {
+        self.convert_rows(move |row| row.get::<U>(column))
+    }
+
+    fn convert_rows<'this, U: 'this>(
+        &'this mut self,
+        convert: impl Fn(Row) -> Result<U, DeError> + 'this,
+    ) -> impl TryStream<Ok = U, Error = Error> + 'this {
+        try_unfold((self, convert), |(stream, de)| async move {
+            match stream.next().await? {
+                Some(row) => match de(row) {
+                    Ok(res) => Ok(Some((res, (stream, de)))),
+                    Err(e) => Err(Error::DeserializationError(e)),
+                },
+                None => Ok(None),
+            }
+        })
+    }
+}
+
+impl<T> Stream for DetachedRowStream<T> {
+    type Item = Result<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if this.pending.is_none() {
+            let Some(mut inner) = this.inner.take() else {
+                return Poll::Ready(Some(Err(Error::ConnectionError)));
+            };
+            if let Some(row) = inner.stream.pop() {
+                this.inner = Some(inner);
+                return Poll::Ready(Some(
+                    (this.convert)(row).map_err(Error::DeserializationError),
+                ));
+            }
+            this.pending = Some(Box::pin(async move {
+                let row = inner.stream.next(&mut inner.connection).await;
+                (inner, row)
+            }));
+        }
+
+        let fetch = this.pending.as_mut().expect("a fetch is pending");
+        let (inner, row) = ready!(fetch.as_mut().poll(cx));
+        this.pending = None;
+        this.inner = Some(inner);
+        Poll::Ready(row.transpose().map(|row| {
+            row.and_then(|row| (this.convert)(row).map_err(Error::DeserializationError))
+        }))
+    }
+}
+
+impl<T> Drop for DetachedRowStream<T> {
+    fn drop(&mut self) {
+        match (&self.pending, &self.inner) {
+            // all records have been received, the connection can be returned right away
+            (None, Some(inner)) if inner.stream.state != State::Ready => return,
+            (None, None) => return,
+            _ => {}
+        }
+        let pending = self.pending.take();
+        let inner = self.inner.take();
+        // a fetch that is interrupted would leave a partial response on the connection,
+        // so it is completed and the remaining records are discarded in the background
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            // the pool resets the connection before it is handed out again
+            return;
+        };
+        runtime.spawn(async move {
+            let inner = match (pending, inner) {
+                (Some(fetch), _) => fetch.await.0,
+                (None, inner) => inner.expect("stream is available without a fetch"),
+            };
+            inner.release().await;
+        });
     }
 }
 
@@ -480,3 +674,219 @@ enum State {
     Ready,
     Complete(BoxedSummary),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_server::{self, TestServer, DISCARD, PULL, RESET, RUN},
+        version::Version,
+        BoltType, ConfigBuilder, Graph,
+    };
+    use futures::{StreamExt, TryStreamExt as _};
+    use std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicI64, Ordering},
+            Mutex,
+        },
+        time::Duration,
+    };
+    use tokio::time::timeout;
+
+    /// Answers every query with the numbers 1 to 3, in batches of two.
+    /// Every query gets its own id, which the client sends with `PULL`.
+    async fn counting_server() -> TestServer {
+        counting_server_with(|| {}).await
+    }
+
+    /// Like [`counting_server`], but calls `before_last_batch` before sending the last batch.
+    async fn counting_server_with(
+        before_last_batch: impl Fn() + Send + Sync + 'static,
+    ) -> TestServer {
+        let runs = AtomicI64::new(0);
+        let pulls = Mutex::new(HashMap::<i64, usize>::new());
+        TestServer::start(Version::V4_4, move |request| match request.signature {
+            RUN => Some(vec![test_server::success([
+                ("fields", BoltType::List(vec![BoltType::from("n")].into())),
+                ("qid", runs.fetch_add(1, Ordering::SeqCst).into()),
+            ])]),
+            PULL => {
+                let qid = request.map(0).get::<i64>("qid").unwrap();
+                let mut pulls = pulls.lock().unwrap();
+                let pull = pulls.entry(qid).or_default();
+                *pull += 1;
+                Some(if *pull == 1 {
+                    vec![
+                        test_server::record([BoltType::from(1)]),
+                        test_server::record([BoltType::from(2)]),
+                        test_server::success([("has_more", true.into())]),
+                    ]
+                } else {
+                    before_last_batch();
+                    vec![
+                        test_server::record([BoltType::from(3)]),
+                        test_server::success([]),
+                    ]
+                })
+            }
+            _ => None,
+        })
+        .await
+    }
+
+    fn connect(server: &TestServer) -> Graph {
+        let config = ConfigBuilder::default()
+            .uri(server.uri())
+            .user("neo4j")
+            .password("neo4j")
+            .max_connections(1)
+            .fetch_size(2)
+            .build()
+            .unwrap();
+        Graph::connect(config).unwrap()
+    }
+
+    async fn typed_numbers(graph: &Graph) -> Result<DetachedRowStream<i64>> {
+        let rows = graph.execute("RETURN n").await?;
+        Ok(rows.into_typed())
+    }
+
+    #[tokio::test]
+    async fn streams_rows_by_value() {
+        let server = counting_server().await;
+        let graph = connect(&server);
+
+        let rows = graph.execute("RETURN n").await.unwrap();
+        let rows = rows.try_collect::<Vec<_>>().await.unwrap();
+        let numbers = rows
+            .iter()
+            .map(|row| row.get::<i64>("n").unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(numbers, [1, 2, 3]);
+
+        let numbers = tokio::spawn(async move {
+            typed_numbers(&graph)
+                .await
+                .unwrap()
+                .try_collect::<Vec<_>>()
+                .await
+        })
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(numbers, [1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn inherent_methods_continue_after_polling() {
+        let server = counting_server().await;
+        let graph = connect(&server);
+
+        let mut numbers = typed_numbers(&graph).await.unwrap();
+        assert_eq!(numbers.next_as::<i64>().await.unwrap(), 1);
+        assert_eq!(numbers.pop_as::<i64>().unwrap(), 2);
+        assert_eq!(StreamExt::next(&mut numbers).await.unwrap().unwrap(), 3);
+        assert!(StreamExt::next(&mut numbers).await.is_none());
+        numbers.finish().await.unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn cancelled_calls_do_not_lose_a_pending_fetch() {
+        let (release, gate) = std::sync::mpsc::channel::<()>();
+        let gate = Mutex::new(gate);
+        let server = counting_server_with(move || {
+            tokio::task::block_in_place(|| gate.lock().unwrap().recv().unwrap())
+        })
+        .await;
+        let graph = connect(&server);
+
+        let mut numbers = typed_numbers(&graph).await.unwrap();
+        assert_eq!(numbers.next_as::<i64>().await.unwrap(), 1);
+        assert_eq!(numbers.pop_as::<i64>().unwrap(), 2);
+
+        // the server holds back the last batch, so that both calls are cancelled mid-fetch
+        let wait = Duration::from_millis(50);
+        assert!(timeout(wait, StreamExt::next(&mut numbers)).await.is_err());
+        assert!(timeout(wait, numbers.next()).await.is_err());
+
+        release.send(()).unwrap();
+        let row = numbers.next().await.unwrap().unwrap();
+        assert_eq!(row.get::<i64>("n").unwrap(), 3);
+        assert!(numbers.next().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn dropping_mid_stream_releases_the_connection() {
+        let server = counting_server().await;
+        let graph = connect(&server);
+
+        let mut numbers = typed_numbers(&graph).await.unwrap();
+        assert_eq!(numbers.next_as::<i64>().await.unwrap(), 1);
+        drop(numbers);
+
+        // waits for the connection, which is released once the remaining records are discarded
+        let rows = graph.execute("RETURN n").await.unwrap();
+        let rows = rows
+            .into_typed::<i64>()
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(rows, [1, 2, 3]);
+
+        let requests = server.requests();
+        assert!(requests.iter().all(|request| request.connection == 0));
+        let signatures = requests
+            .iter()
+            .map(|request| request.signature)
+            .filter(|signature| matches!(*signature, RUN | PULL | DISCARD | RESET))
+            .collect::<Vec<_>>();
+        assert_eq!(signatures, [RUN, PULL, DISCARD, RESET, RUN, PULL, PULL]);
+    }
+
+    #[tokio::test]
+    async fn dropping_a_consumed_stream_discards_nothing() {
+        let server = counting_server().await;
+        let graph = connect(&server);
+
+        let rows = typed_numbers(&graph).await.unwrap();
+        assert_eq!(rows.try_collect::<Vec<_>>().await.unwrap(), [1, 2, 3]);
+        graph.run("RETURN 1").await.unwrap();
+
+        let discards = server
+            .requests()
+            .iter()
+            .filter(|request| request.signature == DISCARD)
+            .count();
+        // only the one of `run`
+        assert_eq!(discards, 1);
+    }
+
+    #[tokio::test]
+    async fn discard_sends_the_qid_of_the_stream() {
+        let server = counting_server().await;
+        let graph = connect(&server);
+
+        let mut txn = graph.start_txn().await.unwrap();
+        let mut first = txn.execute("RETURN n").await.unwrap();
+        let mut second = txn.execute("RETURN n").await.unwrap();
+        assert_eq!(first.next_as::<i64>(txn.handle()).await.unwrap(), 1);
+
+        first.discard(txn.handle()).await.unwrap();
+        assert!(first.next(txn.handle()).await.unwrap().is_none());
+        let discard = server.requests().pop().unwrap();
+        assert_eq!(discard.signature, DISCARD);
+        assert_eq!(discard.map(0).get::<i64>("qid").unwrap(), 0);
+
+        // the records of the first query do not leak into the second one
+        let rows = second
+            .into_stream_as::<i64>(txn.handle())
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(rows, [1, 2, 3]);
+        let pull = server.requests().pop().unwrap();
+        assert_eq!(pull.map(0).get::<i64>("qid").unwrap(), 1);
+        txn.commit().await.unwrap();
+    }
+}
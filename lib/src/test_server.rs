@@ -0,0 +1,222 @@
+//! A scripted Bolt server to test the client against without a running Neo4j.
+
+use crate::types::{BoltMap, BoltString, BoltType};
+use crate::version::Version;
+use bytes::{BufMut, Bytes, BytesMut};
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+pub(crate) const HELLO: u8 = 0x01;
+pub(crate) const GOODBYE: u8 = 0x02;
+pub(crate) const RESET: u8 = 0x0F;
+pub(crate) const RUN: u8 = 0x10;
+pub(crate) const BEGIN: u8 = 0x11;
+pub(crate) const COMMIT: u8 = 0x12;
+pub(crate) const ROLLBACK: u8 = 0x13;
+pub(crate) const DISCARD: u8 = 0x2F;
+pub(crate) const PULL: u8 = 0x3F;
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+pub(crate) const ROUTE: u8 = 0x66;
+pub(crate) const LOGON: u8 = 0x6A;
+pub(crate) const LOGOFF: u8 = 0x6B;
+
+/// A message that the server received.
+#[derive(Clone, Debug)]
+pub(crate) struct Request {
+    /// The number of the connection, in the order they were accepted.
+    pub(crate) connection: usize,
+    pub(crate) signature: u8,
+    pub(crate) fields: Vec<BoltType>,
+}
+
+impl Request {
+    /// The map field at `index`, e.g. the metadata of a `HELLO` or `LOGON` message.
+    pub(crate) fn map(&self, index: usize) -> &BoltMap {
+        match &self.fields[index] {
+            BoltType::Map(map) => map,
+            otherwise => panic!("expected a map, got {otherwise:?}"),
+        }
+    }
+}
+
+type Handler = dyn Fn(&Request) -> Option<Vec<Bytes>> + Send + Sync;
+
+/// Answers the messages of every connection with the responses of a handler,
+/// or with a plain `SUCCESS` if the handler returns `None`.
+pub(crate) struct TestServer {
+    pub(crate) addr: SocketAddr,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl TestServer {
+    pub(crate) async fn start(
+        version: Version,
+        handler: impl Fn(&Request) -> Option<Vec<Bytes>> + Send + Sync + 'static,
+    ) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler: Arc<Handler> = Arc::new(handler);
+
+        let log = Arc::clone(&requests);
+        tokio::spawn(async move {
+            for connection in 0.. {
+                let Ok((stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let log = Arc::clone(&log);
+                let handler = Arc::clone(&handler);
+                tokio::spawn(async move {
+                    // a failing connection just ends, the client sees the error
+                    let _ = serve(stream, version, connection, log, handler).await;
+                });
+            }
+        });
+
+        Self { addr, requests }
+    }
+
+    pub(crate) fn uri(&self) -> String {
+        format!("bolt://{}", self.addr)
+    }
+
+    /// All messages received so far, in order.
+    pub(crate) fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Waits until the server received a message with `signature`,
+    /// e.g. one that the client sends without waiting for a response.
+    pub(crate) async fn wait_for(&self, signature: u8) {
+        for _ in 0..100 {
+            if self.requests().iter().any(|r| r.signature == signature) {
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        panic!("no message with signature {signature:#04x} received");
+    }
+}
+
+async fn serve(
+    mut stream: TcpStream,
+    version: Version,
+    connection: usize,
+    log: Arc<Mutex<Vec<Request>>>,
+    handler: Arc<Handler>,
+) -> std::io::Result<()> {
+    let mut handshake = [0; 20];
+    stream.read_exact(&mut handshake).await?;
+    stream.write_all(&version.to_bytes()).await?;
+
+    loop {
+        let mut message = BytesMut::new();
+        loop {
+            let size = stream.read_u16().await? as usize;
+            if size == 0 {
+                break;
+            }
+            let start = message.len();
+            message.resize(start + size, 0);
+            stream.read_exact(&mut message[start..]).await?;
+        }
+
+        let mut message = message.freeze();
+        let marker = message[0];
+        let signature = message[1];
+        let mut input = message.split_off(2);
+        let fields = (0..marker & 0x0F)
+            .map(|_| BoltType::parse(version, &mut input).unwrap())
+            .collect();
+        let request = Request {
+            connection,
+            signature,
+            fields,
+        };
+        log.lock().unwrap().push(request.clone());
+
+        if signature == GOODBYE {
+            return Ok(());
+        }
+
+        let responses = handler(&request).unwrap_or_else(|| match signature {
+            HELLO => vec![success([
+                ("server", "Neo4j/5.0.0".into()),
+                ("connection_id", format!("bolt-{connection}").into()),
+            ])],
+            _ => vec![success([])],
+        });
+        for response in responses {
+            for chunk in response.chunks(u16::MAX as usize) {
+                stream.write_u16(chunk.len() as u16).await?;
+                stream.write_all(chunk).await?;
+            }
+            stream.write_u16(0).await?;
+        }
+        stream.flush().await?;
+    }
+}
+
+fn message<const N: usize>(signature: u8, fields: [BoltType; N]) -> Bytes {
+    let mut bytes = BytesMut::new();
+    bytes.put_u8(0xB0 | N as u8);
+    bytes.put_u8(signature);
+    for field in fields {
+        field.write_into(Version::V4_4, &mut bytes).unwrap();
+    }
+    bytes.freeze()
+}
+
+pub(crate) fn success<const N: usize>(metadata: [(&str, BoltType); N]) -> Bytes {
+    let metadata = metadata
+        .into_iter()
+        .map(|(key, value)| (BoltString::from(key), value))
+        .collect::<BoltMap>();
+    message(0x70, [BoltType::Map(metadata)])
+}
+
+pub(crate) fn record(values: impl IntoIterator<Item = BoltType>) -> Bytes {
+    let values = values.into_iter().collect::<Vec<_>>();
+    message(0x71, [BoltType::List(values.into())])
+}
+
+/// The response to a `ROUTE` request, with the server at `address` in every role.
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+pub(crate) fn routing_table(address: &str, db: &str) -> Bytes {
+    let map = |entries: Vec<(&str, BoltType)>| {
+        BoltType::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| (BoltString::from(key), value))
+                .collect(),
+        )
+    };
+    let servers = ["WRITE", "READ", "ROUTE"].map(|role| {
+        map(vec![
+            ("addresses", vec![address].into()),
+            ("role", role.into()),
+        ])
+    });
+    let rt = map(vec![
+        ("ttl", BoltType::from(300)),
+        ("db", db.into()),
+        ("servers", BoltType::List(servers.to_vec().into())),
+    ]);
+    success([("rt", rt)])
+}
+
+pub(crate) fn failure(code: &str, description: &str) -> Bytes {
+    let metadata = [
+        (BoltString::from("code"), BoltType::from(code)),
+        (BoltString::from("message"), BoltType::from(description)),
+    ]
+    .into_iter()
+    .collect::<BoltMap>();
+    message(0x7F, [BoltType::Map(metadata)])
+}
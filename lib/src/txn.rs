@@ -1,14 +1,19 @@
 #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
-use crate::messages::{BoltRequest, BoltResponse};
+use crate::messages::{BeginOpts, BoltRequest, BoltResponse};
 #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
 use {
     crate::bolt::{Begin, Commit, Rollback, Summary},
-    crate::bookmarks::Bookmark,
+    crate::bookmarks::{Bookmark, BookmarkManager},
     log::debug,
+    std::sync::Arc,
 };
 
 use crate::{
-    config::Database, errors::Result, pool::ManagedConnection, query::Query, stream::RowStream,
+    config::{Database, TransactionConfig},
+    errors::{Error, Result},
+    pool::ManagedConnection,
+    query::Query,
+    stream::RowStream,
     Operation, RunResult,
 };
 
@@ -23,6 +28,18 @@ pub struct Txn {
     operation: Operation,
     #[allow(dead_code)]
     bookmark: Option<String>,
+    /// Set once a query has been interrupted, which rolled back the transaction on the server.
+    terminated: bool,
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    tracked_bookmarks: Option<TrackedBookmarks>,
+}
+
+/// The bookmarks a transaction waited for, which are replaced in their manager once it commits.
+#[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+struct TrackedBookmarks {
+    manager: Arc<dyn BookmarkManager>,
+    db: Option<Database>,
+    previous: Vec<String>,
 }
 
 impl Txn {
@@ -32,8 +49,18 @@ impl Txn {
         fetch_size: usize,
         mut connection: ManagedConnection,
         operation: Operation,
+        imp_user: Option<&str>,
+        txn_config: &TransactionConfig,
     ) -> Result<Self> {
-        let begin = BoltRequest::begin(db.as_deref());
+        if imp_user.is_some() {
+            connection.check_impersonation()?;
+        }
+        let begin = BoltRequest::begin(BeginOpts {
+            db: db.as_deref(),
+            mode: operation,
+            imp_user,
+            txn_config: Some(txn_config),
+        });
         match connection.send_recv(begin).await? {
             BoltResponse::Success(_) => Ok(Txn {
                 db,
@@ -41,6 +68,7 @@ impl Txn {
                 connection,
                 operation,
                 bookmark: None,
+                terminated: false,
             }),
             msg => Err(msg.into_error("BEGIN")),
         }
@@ -53,11 +81,24 @@ impl Txn {
         mut connection: ManagedConnection,
         operation: Operation,
         bookmarks: &[String],
+        imp_user: Option<&str>,
+        txn_config: &TransactionConfig,
     ) -> Result<Self> {
         debug!("Starting transaction with bookmarks: {:?}", bookmarks);
-        let begin = Begin::builder(db.as_deref())
+        let mut begin = Begin::builder(db.as_deref())
             .with_bookmarks(bookmarks.to_vec())
-            .build(connection.version());
+            .with_mode(if operation.is_read() { "r" } else { "w" });
+        if let Some(imp_user) = imp_user {
+            connection.check_impersonation()?;
+            begin = begin.with_imp_user(imp_user);
+        }
+        if let Some(timeout) = txn_config.timeout_millis() {
+            begin = begin.with_tx_timeout(u32::try_from(timeout).unwrap_or(u32::MAX));
+        }
+        if let Some(metadata) = txn_config.metadata() {
+            begin = begin.with_tx_metadata(metadata.value.clone());
+        }
+        let begin = begin.build(connection.version());
         match connection.send_recv_as(begin).await? {
             Summary::Success(response) => Ok(Txn {
                 db: response.metadata.db.or(db),
@@ -65,12 +106,30 @@ impl Txn {
                 connection,
                 operation,
                 bookmark: None,
+                terminated: false,
+                tracked_bookmarks: None,
             }),
             Summary::Ignored => Err(crate::errors::Error::Ignored("Failed to start transaction")),
             Summary::Failure(failure) => Err(failure.into_error()),
         }
     }
 
+    /// Reports the bookmark of this transaction to `manager` once it is committed,
+    /// replacing the `previous` bookmarks of `db` that it waited for.
+    #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
+    pub(crate) fn track_bookmarks(
+        &mut self,
+        manager: Arc<dyn BookmarkManager>,
+        db: Option<Database>,
+        previous: Vec<String>,
+    ) {
+        self.tracked_bookmarks = Some(TrackedBookmarks {
+            manager,
+            db,
+            previous,
+        });
+    }
+
     #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
     /// Runs multiple queries one after the other in the same connection,
     /// merging all counters from each result summary.
@@ -101,6 +160,7 @@ impl Txn {
 
     /// Runs a single query and discards the stream.
     pub async fn run(&mut self, q: impl Into<Query>) -> Result<RunResult> {
+        self.check_terminated()?;
         let mut query = q.into();
         if let Some(db) = self.db.as_ref() {
             query = query.extra("db", db.to_string());
@@ -118,12 +178,13 @@ impl Txn {
                 self.save_bookmark_state(&result);
                 Ok(result)
             }
-            Err(e) => Err(e),
+            Err(e) => Err(self.interrupted(e)),
         }
     }
 
     /// Executes a query and returns a [`RowStream`]
     pub async fn execute(&mut self, q: impl Into<Query>) -> Result<RowStream> {
+        self.check_terminated()?;
         let mut query = q.into();
         if let Some(db) = self.db.as_ref() {
             query = query.extra("db", db.to_string());
@@ -138,11 +199,13 @@ impl Txn {
         query
             .execute_mut(self.fetch_size, &mut self.connection)
             .await
+            .map_err(|e| self.interrupted(e))
     }
 
     /// Commits the transaction in progress
     #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
     pub async fn commit(mut self) -> Result<()> {
+        self.check_terminated()?;
         let commit = BoltRequest::commit();
         match self.connection.send_recv(commit).await? {
             BoltResponse::Success(_) => Ok(()),
@@ -152,9 +215,16 @@ impl Txn {
 
     #[cfg(feature = "unstable-bolt-protocol-impl-v2")]
     pub async fn commit(mut self) -> Result<Option<String>> {
+        self.check_terminated()?;
         match self.connection.send_recv_as(Commit).await? {
             Summary::Success(resp) => {
                 self.save_bookmark_state(&resp.metadata);
+                if let (Some(tracked), Some(bookmark)) = (&self.tracked_bookmarks, &self.bookmark) {
+                    tracked
+                        .manager
+                        .update_bookmarks(tracked.db.as_deref(), &tracked.previous, bookmark)
+                        .await;
+                }
                 Ok(self.bookmark)
             }
             msg => Err(msg.into_error("COMMIT")),
@@ -162,7 +232,12 @@ impl Txn {
     }
 
     /// rollback/abort the current transaction
+    ///
+    /// A transaction that has been rolled back by an interrupted query is not rolled back again.
     pub async fn rollback(mut self) -> Result<()> {
+        if self.terminated {
+            return Ok(());
+        }
         #[cfg(not(feature = "unstable-bolt-protocol-impl-v2"))]
         {
             let rollback = BoltRequest::rollback();
@@ -181,6 +256,24 @@ impl Txn {
         }
     }
 
+    /// Fails once a query of this transaction has been interrupted.
+    fn check_terminated(&self) -> Result<()> {
+        if self.terminated {
+            Err(Error::TransactionTerminated)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Records that the `RESET` of a query that timed out or was cancelled
+    /// rolled back this transaction.
+    fn interrupted(&mut self, error: Error) -> Error {
+        if matches!(error, Error::Timeout(_) | Error::Cancelled) {
+            self.terminated = true;
+        }
+        error
+    }
+
     pub fn handle(&mut self) -> &mut impl TransactionHandle {
         self
     }
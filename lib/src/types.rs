@@ -76,7 +76,7 @@ impl Display for BoltType {
 }
 
 impl BoltType {
-    fn write_into(&self, version: Version, bytes: &mut BytesMut) -> Result<()> {
+    pub(crate) fn write_into(&self, version: Version, bytes: &mut BytesMut) -> Result<()> {
         match self {
             BoltType::Null(t) => t.write_into(version, bytes),
             BoltType::Boolean(t) => t.write_into(version, bytes),
@@ -102,7 +102,7 @@ impl BoltType {
         }
     }
 
-    fn parse(version: Version, input: &mut Bytes) -> Result<BoltType> {
+    pub(crate) fn parse(version: Version, input: &mut Bytes) -> Result<BoltType> {
         let bolt_type = match input {
             input if BoltInteger::can_parse(version, input) => {
                 BoltType::Integer(BoltInteger::parse(version, input)?)
@@ -1,11 +1,17 @@
 use crate::errors::Error;
 use crate::types::*;
-use chrono::{DateTime, FixedOffset, NaiveDateTime, Offset, Timelike};
+use crate::version::Version;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Offset, TimeZone, Timelike};
 use neo4rs_macros::BoltStruct;
 use std::convert::TryInto;
 
-#[derive(Debug, PartialEq, Eq, Clone, BoltStruct)]
-#[signature(0xB3, 0x46)]
+/// A date time with a fixed offset.
+///
+/// The `seconds` are always stored as local seconds (UTC seconds plus the offset),
+/// which is what Bolt 4.x sends on the wire.
+/// Bolt 5.0 and newer send UTC seconds, which are converted when reading and writing.
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct BoltDateTime {
     pub(crate) seconds: BoltInteger,
     pub(crate) nanoseconds: BoltInteger,
@@ -19,14 +25,111 @@ pub struct BoltLocalDateTime {
     pub(crate) nanoseconds: BoltInteger,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, BoltStruct)]
-#[signature(0xB3, 0x66)]
+/// A date time with a named time zone.
+///
+/// The `seconds` are always stored as local seconds in that time zone,
+/// which is what Bolt 4.x sends on the wire.
+/// Bolt 5.0 and newer send UTC seconds, which are converted when reading and writing.
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct BoltDateTimeZoneId {
     pub(crate) seconds: BoltInteger,
     pub(crate) nanoseconds: BoltInteger,
     pub(crate) tz_id: BoltString,
 }
 
+impl BoltWireFormat for BoltDateTime {
+    fn can_parse(_version: Version, input: &[u8]) -> bool {
+        input.len() >= 2 && input[0] == 0xB3 && (input[1] == 0x46 || input[1] == 0x49)
+    }
+
+    fn parse(version: Version, input: &mut Bytes) -> Result<Self> {
+        input.get_u8();
+        let utc = input.get_u8() == 0x49;
+        let seconds = BoltInteger::parse(version, input)?;
+        let nanoseconds = BoltInteger::parse(version, input)?;
+        let tz_offset_seconds = BoltInteger::parse(version, input)?;
+        let seconds = if utc {
+            BoltInteger::new(seconds.value + tz_offset_seconds.value)
+        } else {
+            seconds
+        };
+        Ok(BoltDateTime {
+            seconds,
+            nanoseconds,
+            tz_offset_seconds,
+        })
+    }
+
+    fn write_into(&self, version: Version, bytes: &mut BytesMut) -> Result<()> {
+        bytes.reserve(2);
+        bytes.put_u8(0xB3);
+        if version >= Version::V5_0 {
+            bytes.put_u8(0x49);
+            BoltInteger::new(self.seconds.value - self.tz_offset_seconds.value)
+                .write_into(version, bytes)?;
+        } else {
+            bytes.put_u8(0x46);
+            self.seconds.write_into(version, bytes)?;
+        }
+        self.nanoseconds.write_into(version, bytes)?;
+        self.tz_offset_seconds.write_into(version, bytes)?;
+        Ok(())
+    }
+}
+
+impl BoltWireFormat for BoltDateTimeZoneId {
+    fn can_parse(_version: Version, input: &[u8]) -> bool {
+        input.len() >= 2 && input[0] == 0xB3 && (input[1] == 0x66 || input[1] == 0x69)
+    }
+
+    fn parse(version: Version, input: &mut Bytes) -> Result<Self> {
+        input.get_u8();
+        let utc = input.get_u8() == 0x69;
+        let seconds = BoltInteger::parse(version, input)?;
+        let nanoseconds = BoltInteger::parse(version, input)?;
+        let tz_id = BoltString::parse(version, input)?;
+        let seconds = if utc {
+            let tz = Self::parse_tz(&tz_id)?;
+            let local = tz
+                .timestamp_opt(seconds.value, 0)
+                .single()
+                .ok_or(Error::ConversionError)?
+                .naive_local();
+            BoltInteger::new(local.and_utc().timestamp())
+        } else {
+            seconds
+        };
+        Ok(BoltDateTimeZoneId {
+            seconds,
+            nanoseconds,
+            tz_id,
+        })
+    }
+
+    fn write_into(&self, version: Version, bytes: &mut BytesMut) -> Result<()> {
+        bytes.reserve(2);
+        bytes.put_u8(0xB3);
+        if version >= Version::V5_0 {
+            let tz = Self::parse_tz(&self.tz_id)?;
+            let local = DateTime::from_timestamp(self.seconds.value, 0)
+                .ok_or(Error::ConversionError)?
+                .naive_utc();
+            let utc = tz
+                .from_local_datetime(&local)
+                .earliest()
+                .ok_or(Error::ConversionError)?;
+            bytes.put_u8(0x69);
+            BoltInteger::new(utc.timestamp()).write_into(version, bytes)?;
+        } else {
+            bytes.put_u8(0x66);
+            self.seconds.write_into(version, bytes)?;
+        }
+        self.nanoseconds.write_into(version, bytes)?;
+        self.tz_id.write_into(version, bytes)?;
+        Ok(())
+    }
+}
+
 impl BoltDateTime {
     pub(crate) fn try_to_chrono(&self) -> Result<DateTime<FixedOffset>> {
         self.try_into()
@@ -47,6 +150,10 @@ impl BoltDateTimeZoneId {
     pub fn tz_id(&self) -> &str {
         &self.tz_id.value
     }
+
+    fn parse_tz(tz_id: &BoltString) -> Result<chrono_tz::Tz> {
+        tz_id.value.parse().map_err(|_| Error::ConversionError)
+    }
 }
 
 impl From<(NaiveDateTime, &str)> for BoltDateTimeZoneId {
@@ -178,8 +285,6 @@ impl TryFrom<&BoltDateTime> for DateTime<FixedOffset> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::version::Version;
-    use bytes::Bytes;
 
     #[test]
     fn should_serialize_a_datetime() {
@@ -269,4 +374,64 @@ mod tests {
         assert_eq!(datetime.to_string(), "2015-07-01 08:59:60.123");
         assert_eq!(zone_id, "Europe/Paris");
     }
+
+    #[test]
+    fn should_serialize_a_utc_datetime_for_v5() {
+        let date: BoltDateTime = DateTime::parse_from_rfc2822("Wed, 24 Jun 2015 12:50:35 +0100")
+            .unwrap()
+            .into();
+
+        assert_eq!(
+            date.into_bytes(Version::V5_0).unwrap(),
+            Bytes::from_static(&[
+                0xB3, 0x49, 0xCA, 0x55, 0x8A, 0x99, 0x8B, 0x00, 0xC9, 0x0E, 0x10,
+            ])
+        );
+    }
+
+    #[test]
+    fn should_deserialize_a_utc_datetime() {
+        let mut bytes = Bytes::from_static(&[
+            0xB3, 0x49, 0xCA, 0x55, 0x8A, 0x99, 0x8B, 0x00, 0xC9, 0x0E, 0x10,
+        ]);
+
+        let datetime = BoltDateTime::parse(Version::V5_0, &mut bytes).unwrap();
+        assert_eq!(datetime.seconds, BoltInteger::new(0x558A_A79B));
+
+        let datetime: DateTime<FixedOffset> = datetime.try_into().unwrap();
+        assert_eq!(datetime.to_rfc2822(), "Wed, 24 Jun 2015 12:50:35 +0100");
+    }
+
+    #[test]
+    fn should_serialize_a_utc_datetime_with_zoneid_for_v5() {
+        let datetime =
+            NaiveDateTime::parse_from_str("2015-07-01 08:59:60.123", "%Y-%m-%d %H:%M:%S%.f")
+                .unwrap();
+
+        let date: BoltDateTimeZoneId = (datetime, "Europe/Paris").into();
+
+        assert_eq!(
+            date.into_bytes(Version::V5_0).unwrap(),
+            Bytes::from_static(&[
+                0xB3, 0x69, 0xCA, 0x55, 0x93, 0x8F, 0xEF, 0xCA, 0x42, 0xEF, 0x9E, 0xC0, 0x8C, 0x45,
+                0x75, 0x72, 0x6F, 0x70, 0x65, 0x2F, 0x50, 0x61, 0x72, 0x69, 0x73,
+            ])
+        );
+    }
+
+    #[test]
+    fn should_deserialize_a_utc_datetime_with_zoneid() {
+        let mut bytes = Bytes::from_static(&[
+            0xB3, 0x69, 0xCA, 0x55, 0x93, 0x8F, 0xEF, 0xCA, 0x42, 0xEF, 0x9E, 0xC0, 0x8C, 0x45,
+            0x75, 0x72, 0x6F, 0x70, 0x65, 0x2F, 0x50, 0x61, 0x72, 0x69, 0x73,
+        ]);
+
+        let (datetime, zone_id) = BoltDateTimeZoneId::parse(Version::V5_0, &mut bytes)
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        assert_eq!(datetime.to_string(), "2015-07-01 08:59:60.123");
+        assert_eq!(zone_id, "Europe/Paris");
+    }
 }
@@ -1,13 +1,19 @@
-use crate::types::{serde::DeError, BoltInteger, BoltList, BoltMap, BoltType, Result};
+use crate::{
+    types::{
+        serde::DeError, BoltInteger, BoltList, BoltMap, BoltString, BoltType, BoltWireFormat,
+        Result,
+    },
+    version::Version,
+};
 use ::serde::Deserialize;
-use neo4rs_macros::BoltStruct;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 
-#[derive(Debug, PartialEq, Clone, BoltStruct)]
-#[signature(0xB3, 0x4E)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct BoltNode {
     pub id: BoltInteger,
     pub labels: BoltList,
     pub properties: BoltMap,
+    pub(crate) element_id: Option<BoltString>,
 }
 
 impl BoltNode {
@@ -16,8 +22,53 @@ impl BoltNode {
             id,
             labels,
             properties,
+            element_id: None,
         }
     }
+
+    /// Element id of the node, only sent by servers that speak Bolt 5.0 or newer.
+    pub fn element_id(&self) -> Option<&str> {
+        self.element_id.as_ref().map(|id| id.value.as_str())
+    }
+}
+
+impl BoltWireFormat for BoltNode {
+    fn can_parse(_version: Version, input: &[u8]) -> bool {
+        input.len() >= 2 && (input[0] == 0xB3 || input[0] == 0xB4) && input[1] == 0x4E
+    }
+
+    fn parse(version: Version, input: &mut Bytes) -> Result<Self> {
+        let fields = input.get_u8() & 0x0F;
+        input.get_u8();
+        let id = BoltInteger::parse(version, input)?;
+        let labels = BoltList::parse(version, input)?;
+        let properties = BoltMap::parse(version, input)?;
+        let element_id = if fields > 3 {
+            Option::<BoltString>::parse(version, input)?
+        } else {
+            None
+        };
+        Ok(BoltNode {
+            id,
+            labels,
+            properties,
+            element_id,
+        })
+    }
+
+    fn write_into(&self, version: Version, bytes: &mut BytesMut) -> Result<()> {
+        let v5 = version >= Version::V5_0;
+        bytes.reserve(2);
+        bytes.put_u8(if v5 { 0xB4 } else { 0xB3 });
+        bytes.put_u8(0x4E);
+        self.id.write_into(version, bytes)?;
+        self.labels.write_into(version, bytes)?;
+        self.properties.write_into(version, bytes)?;
+        if v5 {
+            self.element_id.write_into(version, bytes)?;
+        }
+        Ok(())
+    }
 }
 
 impl BoltNode {
@@ -38,8 +89,6 @@ impl From<BoltNode> for BoltType {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{types::BoltWireFormat, version::Version};
-    use bytes::Bytes;
 
     #[test]
     fn should_deserialize_a_node() {
@@ -63,11 +112,7 @@ mod tests {
         let id = BoltInteger::new(19);
         let labels = vec!["Person".into()].into();
         let properties = vec![("name".into(), "Mark".into())].into_iter().collect();
-        let node = BoltNode {
-            id,
-            labels,
-            properties,
-        };
+        let node = BoltNode::new(id, labels, properties);
 
         let bytes: Bytes = node.into_bytes(Version::V4_1).unwrap();
 
@@ -79,4 +124,40 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn should_deserialize_a_v5_node() {
+        let mut input = Bytes::from_static(&[
+            0xB4, 0x4E, 0x13, 0x91, 0x86, 0x50, 0x65, 0x72, 0x73, 0x6F, 0x6E, 0xA1, 0x84, 0x6E,
+            0x61, 0x6D, 0x65, 0x84, 0x4D, 0x61, 0x72, 0x6B, 0x84, 0x34, 0x3A, 0x31, 0x39,
+        ]);
+
+        let node: BoltNode = BoltNode::parse(Version::V5_0, &mut input).unwrap();
+
+        assert_eq!(node.id, BoltInteger::new(19));
+        assert_eq!(node.labels, vec!["Person".into()].into());
+        assert_eq!(node.element_id, Some(BoltString::new("4:19")));
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn should_serialize_a_node_per_version() {
+        let mut node = BoltNode::new(BoltInteger::new(19), BoltList::new(), BoltMap::new());
+
+        let bytes: Bytes = node.clone().into_bytes(Version::V4_4).unwrap();
+        assert_eq!(bytes, Bytes::from_static(&[0xB3, 0x4E, 0x13, 0x90, 0xA0]));
+
+        let bytes: Bytes = node.clone().into_bytes(Version::V5_0).unwrap();
+        assert_eq!(
+            bytes,
+            Bytes::from_static(&[0xB4, 0x4E, 0x13, 0x90, 0xA0, 0xC0])
+        );
+
+        node.element_id = Some(BoltString::new("4:19"));
+        let bytes: Bytes = node.into_bytes(Version::V5_4).unwrap();
+        assert_eq!(
+            bytes,
+            Bytes::from_static(&[0xB4, 0x4E, 0x13, 0x90, 0xA0, 0x84, 0x34, 0x3A, 0x31, 0x39])
+        );
+    }
 }
@@ -1,3 +1,5 @@
+use crate::{errors::Result, types::BoltWireFormat, version::Version};
+use bytes::{Bytes, BytesMut};
 use neo4rs_macros::BoltStruct;
 
 #[derive(Debug, PartialEq, Eq, Clone, BoltStruct)]
@@ -10,11 +12,33 @@ impl Default for BoltNull {
     }
 }
 
+impl<T: BoltWireFormat> BoltWireFormat for Option<T> {
+    fn can_parse(version: Version, input: &[u8]) -> bool {
+        BoltNull::can_parse(version, input) || T::can_parse(version, input)
+    }
+
+    fn parse(version: Version, input: &mut Bytes) -> Result<Self> {
+        if BoltNull::can_parse(version, input) {
+            BoltNull::parse(version, input)?;
+            Ok(None)
+        } else {
+            T::parse(version, input).map(Some)
+        }
+    }
+
+    fn write_into(&self, version: Version, bytes: &mut BytesMut) -> Result<()> {
+        match self {
+            Some(value) => value.write_into(version, bytes),
+            None => BoltNull.write_into(version, bytes),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{types::BoltWireFormat, version::Version};
-    use bytes::*;
+    use crate::types::BoltString;
+    use bytes::Bytes;
 
     #[test]
     fn should_serialize_null() {
@@ -22,4 +46,24 @@ mod tests {
         let b: Bytes = null.into_bytes(Version::V4_1).unwrap();
         assert_eq!(&b[..], &[0xC0]);
     }
+
+    #[test]
+    fn should_serialize_optional_values() {
+        let none: Option<BoltString> = None;
+        let b: Bytes = none.into_bytes(Version::V5_0).unwrap();
+        assert_eq!(&b[..], &[0xC0]);
+
+        let some = Some(BoltString::new("a"));
+        let b: Bytes = some.into_bytes(Version::V5_0).unwrap();
+        assert_eq!(&b[..], &[0x81, b'a']);
+    }
+
+    #[test]
+    fn should_deserialize_optional_values() {
+        let mut input = Bytes::from_static(&[0xC0, 0x81, b'a']);
+        let none = Option::<BoltString>::parse(Version::V5_0, &mut input).unwrap();
+        let some = Option::<BoltString>::parse(Version::V5_0, &mut input).unwrap();
+        assert_eq!(none, None);
+        assert_eq!(some, Some(BoltString::new("a")));
+    }
 }
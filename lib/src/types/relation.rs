@@ -1,23 +1,68 @@
-use crate::types::{serde::DeError, BoltInteger, BoltMap, BoltString, BoltType, Result};
+use crate::{
+    types::{serde::DeError, BoltInteger, BoltMap, BoltString, BoltType, BoltWireFormat, Result},
+    version::Version,
+};
 use ::serde::Deserialize;
-use neo4rs_macros::BoltStruct;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 
-#[derive(Debug, PartialEq, Clone, BoltStruct)]
-#[signature(0xB5, 0x52)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct BoltRelation {
     pub id: BoltInteger,
     pub start_node_id: BoltInteger,
     pub end_node_id: BoltInteger,
     pub typ: BoltString,
     pub properties: BoltMap,
+    pub(crate) element_id: Option<BoltString>,
+    pub(crate) start_node_element_id: Option<BoltString>,
+    pub(crate) end_node_element_id: Option<BoltString>,
 }
 
-#[derive(Debug, PartialEq, Clone, BoltStruct)]
-#[signature(0xB3, 0x72)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct BoltUnboundedRelation {
     pub id: BoltInteger,
     pub typ: BoltString,
     pub properties: BoltMap,
+    pub(crate) element_id: Option<BoltString>,
+}
+
+impl BoltRelation {
+    pub fn new(
+        id: BoltInteger,
+        start_node_id: BoltInteger,
+        end_node_id: BoltInteger,
+        typ: BoltString,
+        properties: BoltMap,
+    ) -> Self {
+        BoltRelation {
+            id,
+            start_node_id,
+            end_node_id,
+            typ,
+            properties,
+            element_id: None,
+            start_node_element_id: None,
+            end_node_element_id: None,
+        }
+    }
+
+    /// Element id of the relationship, only sent by servers that speak Bolt 5.0 or newer.
+    pub fn element_id(&self) -> Option<&str> {
+        self.element_id.as_ref().map(|id| id.value.as_str())
+    }
+
+    /// Element id of the start node, only sent by servers that speak Bolt 5.0 or newer.
+    pub fn start_node_element_id(&self) -> Option<&str> {
+        self.start_node_element_id
+            .as_ref()
+            .map(|id| id.value.as_str())
+    }
+
+    /// Element id of the end node, only sent by servers that speak Bolt 5.0 or newer.
+    pub fn end_node_element_id(&self) -> Option<&str> {
+        self.end_node_element_id
+            .as_ref()
+            .map(|id| id.value.as_str())
+    }
 }
 
 impl BoltUnboundedRelation {
@@ -26,7 +71,105 @@ impl BoltUnboundedRelation {
             id,
             typ,
             properties,
+            element_id: None,
+        }
+    }
+
+    /// Element id of the relationship, only sent by servers that speak Bolt 5.0 or newer.
+    pub fn element_id(&self) -> Option<&str> {
+        self.element_id.as_ref().map(|id| id.value.as_str())
+    }
+}
+
+impl BoltWireFormat for BoltRelation {
+    fn can_parse(_version: Version, input: &[u8]) -> bool {
+        input.len() >= 2 && (input[0] == 0xB5 || input[0] == 0xB8) && input[1] == 0x52
+    }
+
+    fn parse(version: Version, input: &mut Bytes) -> Result<Self> {
+        let fields = input.get_u8() & 0x0F;
+        input.get_u8();
+        let id = BoltInteger::parse(version, input)?;
+        let start_node_id = BoltInteger::parse(version, input)?;
+        let end_node_id = BoltInteger::parse(version, input)?;
+        let typ = BoltString::parse(version, input)?;
+        let properties = BoltMap::parse(version, input)?;
+        let (element_id, start_node_element_id, end_node_element_id) = if fields > 5 {
+            (
+                Option::<BoltString>::parse(version, input)?,
+                Option::<BoltString>::parse(version, input)?,
+                Option::<BoltString>::parse(version, input)?,
+            )
+        } else {
+            (None, None, None)
+        };
+        Ok(BoltRelation {
+            id,
+            start_node_id,
+            end_node_id,
+            typ,
+            properties,
+            element_id,
+            start_node_element_id,
+            end_node_element_id,
+        })
+    }
+
+    fn write_into(&self, version: Version, bytes: &mut BytesMut) -> Result<()> {
+        let v5 = version >= Version::V5_0;
+        bytes.reserve(2);
+        bytes.put_u8(if v5 { 0xB8 } else { 0xB5 });
+        bytes.put_u8(0x52);
+        self.id.write_into(version, bytes)?;
+        self.start_node_id.write_into(version, bytes)?;
+        self.end_node_id.write_into(version, bytes)?;
+        self.typ.write_into(version, bytes)?;
+        self.properties.write_into(version, bytes)?;
+        if v5 {
+            self.element_id.write_into(version, bytes)?;
+            self.start_node_element_id.write_into(version, bytes)?;
+            self.end_node_element_id.write_into(version, bytes)?;
         }
+        Ok(())
+    }
+}
+
+impl BoltWireFormat for BoltUnboundedRelation {
+    fn can_parse(_version: Version, input: &[u8]) -> bool {
+        input.len() >= 2 && (input[0] == 0xB3 || input[0] == 0xB4) && input[1] == 0x72
+    }
+
+    fn parse(version: Version, input: &mut Bytes) -> Result<Self> {
+        let fields = input.get_u8() & 0x0F;
+        input.get_u8();
+        let id = BoltInteger::parse(version, input)?;
+        let typ = BoltString::parse(version, input)?;
+        let properties = BoltMap::parse(version, input)?;
+        let element_id = if fields > 3 {
+            Option::<BoltString>::parse(version, input)?
+        } else {
+            None
+        };
+        Ok(BoltUnboundedRelation {
+            id,
+            typ,
+            properties,
+            element_id,
+        })
+    }
+
+    fn write_into(&self, version: Version, bytes: &mut BytesMut) -> Result<()> {
+        let v5 = version >= Version::V5_0;
+        bytes.reserve(2);
+        bytes.put_u8(if v5 { 0xB4 } else { 0xB3 });
+        bytes.put_u8(0x72);
+        self.id.write_into(version, bytes)?;
+        self.typ.write_into(version, bytes)?;
+        self.properties.write_into(version, bytes)?;
+        if v5 {
+            self.element_id.write_into(version, bytes)?;
+        }
+        Ok(())
     }
 }
 
@@ -63,8 +206,6 @@ impl From<BoltUnboundedRelation> for BoltType {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{types::BoltWireFormat, Version};
-    use bytes::Bytes;
 
     #[test]
     fn should_serialize_a_relation() {
@@ -80,6 +221,9 @@ mod tests {
             end_node_id,
             typ,
             properties,
+            element_id: None,
+            start_node_element_id: None,
+            end_node_element_id: None,
         };
 
         let bytes: Bytes = relation.into_bytes(Version::V4_1).unwrap();
@@ -147,4 +291,71 @@ mod tests {
             vec![("name".into(), "Mark".into())].into_iter().collect()
         );
     }
+
+    #[test]
+    fn should_serialize_a_v5_relation() {
+        let relation = BoltRelation {
+            id: BoltInteger::new(42),
+            start_node_id: BoltInteger::new(1),
+            end_node_id: BoltInteger::new(2),
+            typ: BoltString::new("rel"),
+            properties: BoltMap::new(),
+            element_id: Some(BoltString::new("5:42")),
+            start_node_element_id: Some(BoltString::new("4:1")),
+            end_node_element_id: None,
+        };
+
+        let bytes: Bytes = relation.clone().into_bytes(Version::V4_4).unwrap();
+        assert_eq!(
+            bytes,
+            Bytes::from_static(&[0xB5, 0x52, 0x2A, 0x01, 0x02, 0x83, 0x72, 0x65, 0x6C, 0xA0])
+        );
+
+        let bytes: Bytes = relation.into_bytes(Version::V5_0).unwrap();
+        assert_eq!(
+            bytes,
+            Bytes::from_static(&[
+                0xB8, 0x52, 0x2A, 0x01, 0x02, 0x83, 0x72, 0x65, 0x6C, 0xA0, 0x84, 0x35, 0x3A, 0x34,
+                0x32, 0x83, 0x34, 0x3A, 0x31, 0xC0,
+            ])
+        );
+    }
+
+    #[test]
+    fn should_deserialize_a_v5_relation() {
+        let mut input = Bytes::from_static(&[
+            0xB8, 0x52, 0x2A, 0x01, 0x02, 0x83, 0x72, 0x65, 0x6C, 0xA0, 0x84, 0x35, 0x3A, 0x34,
+            0x32, 0x83, 0x34, 0x3A, 0x31, 0x83, 0x34, 0x3A, 0x32,
+        ]);
+
+        let relation: BoltRelation = BoltRelation::parse(Version::V5_0, &mut input).unwrap();
+
+        assert_eq!(relation.id, BoltInteger::new(42));
+        assert_eq!(relation.typ, BoltString::new("rel"));
+        assert_eq!(relation.element_id, Some(BoltString::new("5:42")));
+        assert_eq!(relation.start_node_element_id, Some(BoltString::new("4:1")));
+        assert_eq!(relation.end_node_element_id, Some(BoltString::new("4:2")));
+        assert!(input.is_empty());
+    }
+
+    #[test]
+    fn should_roundtrip_a_v5_unbounded_relation() {
+        let mut relation = BoltUnboundedRelation::new(
+            BoltInteger::new(42),
+            BoltString::new("rel"),
+            BoltMap::new(),
+        );
+        relation.element_id = Some(BoltString::new("5:42"));
+
+        let mut bytes: Bytes = relation.clone().into_bytes(Version::V5_0).unwrap();
+        assert_eq!(
+            bytes,
+            Bytes::from_static(&[
+                0xB4, 0x72, 0x2A, 0x83, 0x72, 0x65, 0x6C, 0xA0, 0x84, 0x35, 0x3A, 0x34, 0x32,
+            ])
+        );
+
+        let parsed = BoltUnboundedRelation::parse(Version::V5_0, &mut bytes).unwrap();
+        assert_eq!(parsed, relation);
+    }
 }
@@ -3,10 +3,13 @@ use crate::types::{
     BoltUnboundedRelation,
 };
 
-use std::result::Result;
+use std::{fmt, result::Result};
 
 use delegate::delegate;
-use serde::{de::Error, Deserialize};
+use serde::{
+    de::{Error, Visitor},
+    Deserialize,
+};
 
 #[derive(Debug, Clone, Default)]
 pub struct BoltNodeBuilder {
@@ -17,12 +20,14 @@ impl BoltNodeBuilder {
     delegate! {
         to self.inner {
             pub fn id<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltInteger, E>) -> Result<(), E>;
+            pub fn element_id<E: Error>(&mut self, read: impl FnOnce() -> Result<Option<BoltString>, E>) -> Result<(), E>;
             pub fn labels<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltList, E>) -> Result<(), E>;
             pub fn properties<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltMap, E>) -> Result<(), E>;
         }
     }
 
-    pub fn build<E: Error>(self) -> Result<BoltNode, E> {
+    pub fn build<E: Error>(mut self) -> Result<BoltNode, E> {
+        let element_id = self.inner.element_id.take().flatten();
         let id = self.inner.id.ok_or_else(|| Error::missing_field("id"))?;
         let labels = self
             .inner
@@ -34,6 +39,7 @@ impl BoltNodeBuilder {
             id,
             labels,
             properties,
+            element_id,
         })
     }
 }
@@ -49,12 +55,18 @@ impl BoltRelationBuilder {
             pub fn id<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltInteger, E>) -> Result<(), E>;
             pub fn start_node_id<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltInteger, E>) -> Result<(), E>;
             pub fn end_node_id<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltInteger, E>) -> Result<(), E>;
+            pub fn element_id<E: Error>(&mut self, read: impl FnOnce() -> Result<Option<BoltString>, E>) -> Result<(), E>;
+            pub fn start_node_element_id<E: Error>(&mut self, read: impl FnOnce() -> Result<Option<BoltString>, E>) -> Result<(), E>;
+            pub fn end_node_element_id<E: Error>(&mut self, read: impl FnOnce() -> Result<Option<BoltString>, E>) -> Result<(), E>;
             pub fn typ<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltString, E>) -> Result<(), E>;
             pub fn properties<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltMap, E>) -> Result<(), E>;
         }
     }
 
-    pub fn build<E: Error>(self) -> Result<BoltRelation, E> {
+    pub fn build<E: Error>(mut self) -> Result<BoltRelation, E> {
+        let element_id = self.inner.element_id.take().flatten();
+        let start_node_element_id = self.inner.start_node_element_id.take().flatten();
+        let end_node_element_id = self.inner.end_node_element_id.take().flatten();
         let id = self.inner.id.ok_or_else(|| Error::missing_field("id"))?;
         let start_node_id = self
             .inner
@@ -73,6 +85,9 @@ impl BoltRelationBuilder {
             end_node_id,
             typ,
             properties,
+            element_id,
+            start_node_element_id,
+            end_node_element_id,
         })
     }
 }
@@ -86,12 +101,14 @@ impl BoltUnboundedRelationBuilder {
     delegate! {
         to self.inner {
             pub fn id<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltInteger, E>) -> Result<(), E>;
+            pub fn element_id<E: Error>(&mut self, read: impl FnOnce() -> Result<Option<BoltString>, E>) -> Result<(), E>;
             pub fn typ<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltString, E>) -> Result<(), E>;
             pub fn properties<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltMap, E>) -> Result<(), E>;
         }
     }
 
-    pub fn build<E: Error>(self) -> Result<BoltUnboundedRelation, E> {
+    pub fn build<E: Error>(mut self) -> Result<BoltUnboundedRelation, E> {
+        let element_id = self.inner.element_id.take().flatten();
         let id = self.inner.id.ok_or_else(|| Error::missing_field("id"))?;
         let typ = self.inner.typ.ok_or_else(|| Error::missing_field("type"))?;
         let properties = self.inner.properties.or_else(Default::default);
@@ -100,6 +117,7 @@ impl BoltUnboundedRelationBuilder {
             id,
             typ,
             properties,
+            element_id,
         })
     }
 }
@@ -172,11 +190,54 @@ impl<'de> Deserialize<'de> for EndNodeId {
     }
 }
 
+macro_rules! optional_element_id {
+    ($($name:ident),+ $(,)?) => {
+        $(
+            pub struct $name(pub Option<BoltString>);
+
+            impl<'de> Deserialize<'de> for $name {
+                fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    struct TheVisitor;
+
+                    impl<'de> Visitor<'de> for TheVisitor {
+                        type Value = $name;
+
+                        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                            formatter.write_str(concat!("an optional ", stringify!($name)))
+                        }
+
+                        fn visit_none<E: Error>(self) -> Result<Self::Value, E> {
+                            Ok($name(None))
+                        }
+
+                        fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+                        where
+                            D: serde::Deserializer<'de>,
+                        {
+                            BoltString::deserialize(deserializer).map(|id| $name(Some(id)))
+                        }
+                    }
+
+                    deserializer.deserialize_newtype_struct(concat!(stringify!($name), "?"), TheVisitor)
+                }
+            }
+        )+
+    };
+}
+
+optional_element_id!(ElementId, StartNodeElementId, EndNodeElementId);
+
 #[derive(Debug, Clone, Default)]
 struct ElementBuilder {
     id: SetOnce<BoltInteger>,
     start_node_id: SetOnce<BoltInteger>,
     end_node_id: SetOnce<BoltInteger>,
+    element_id: SetOnce<Option<BoltString>>,
+    start_node_element_id: SetOnce<Option<BoltString>>,
+    end_node_element_id: SetOnce<Option<BoltString>>,
     labels: SetOnce<BoltList>,
     typ: SetOnce<BoltString>,
     properties: SetOnce<BoltMap>,
@@ -213,6 +274,36 @@ impl ElementBuilder {
         }
     }
 
+    fn element_id<E: Error>(
+        &mut self,
+        read: impl FnOnce() -> Result<Option<BoltString>, E>,
+    ) -> Result<(), E> {
+        match self.element_id.try_insert_with(read)? {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::duplicate_field("element_id")),
+        }
+    }
+
+    fn start_node_element_id<E: Error>(
+        &mut self,
+        read: impl FnOnce() -> Result<Option<BoltString>, E>,
+    ) -> Result<(), E> {
+        match self.start_node_element_id.try_insert_with(read)? {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::duplicate_field("start_node_element_id")),
+        }
+    }
+
+    fn end_node_element_id<E: Error>(
+        &mut self,
+        read: impl FnOnce() -> Result<Option<BoltString>, E>,
+    ) -> Result<(), E> {
+        match self.end_node_element_id.try_insert_with(read)? {
+            Ok(_) => Ok(()),
+            Err(_) => Err(Error::duplicate_field("end_node_element_id")),
+        }
+    }
+
     fn labels<E: Error>(&mut self, read: impl FnOnce() -> Result<BoltList, E>) -> Result<(), E> {
         match self.labels.try_insert_with(read)? {
             Ok(_) => Ok(()),
@@ -256,18 +347,13 @@ impl ElementBuilder {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Default)]
 pub enum SetOnce<T> {
+    #[default]
     Empty,
     Set(T),
 }
 
-impl<T> Default for SetOnce<T> {
-    fn default() -> Self {
-        Self::Empty
-    }
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct SetOnceError;
 
@@ -12,8 +12,8 @@ use crate::{
         serde::Properties, BoltBoolean, BoltBytes, BoltFloat, BoltInteger, BoltList, BoltMap,
         BoltNull, BoltString, BoltType,
     },
-    EndNodeId, Id, Indices, Keys, Labels, Nodes, Offset, Relationships, StartNodeId, Timezone,
-    Type,
+    ElementId, EndNodeElementId, EndNodeId, Id, Indices, Keys, Labels, Nodes, Offset,
+    Relationships, StartNodeElementId, StartNodeId, Timezone, Type,
 };
 
 impl<'de> Deserialize<'de> for BoltString {
@@ -136,6 +136,9 @@ newtype_deser!(
     Id(u64) => Id,
     StartNodeId(u64) => StartNodeId,
     EndNodeId(u64) => EndNodeId,
+    ElementId<T>(T) => ElementId<T>,
+    StartNodeElementId<T>(T) => StartNodeElementId<T>,
+    EndNodeElementId<T>(T) => EndNodeElementId<T>,
     Labels<Coll>(Coll) => Labels<Coll>,
     Type<T>(T) => Type<T>,
     Keys<Coll>(Coll) => Keys<Coll>,
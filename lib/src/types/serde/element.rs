@@ -4,7 +4,7 @@ use crate::types::{
     BoltUnboundedRelation,
 };
 
-use std::{iter, marker::PhantomData, result::Result};
+use std::{array, iter, marker::PhantomData, result::Result};
 
 use delegate::delegate;
 use serde::{
@@ -28,6 +28,9 @@ crate::cenum!(ElementDataKey {
     Id,
     StartNodeId,
     EndNodeId,
+    ElementId,
+    StartNodeElementId,
+    EndNodeElementId,
     Type,
     Labels,
     Properties,
@@ -128,6 +131,27 @@ impl<'de, T: ElementData<'de>> ElementDataDeserializer<'de, T> {
                 };
                 visitation.visit(id, visitor)
             }
+            "ElementId" => self.element_id(ElementDataKey::ElementId, visitor, visitation),
+            "StartNodeElementId" => {
+                self.element_id(ElementDataKey::StartNodeElementId, visitor, visitation)
+            }
+            "EndNodeElementId" => {
+                self.element_id(ElementDataKey::EndNodeElementId, visitor, visitation)
+            }
+            // Used by the builders, element ids are only sent by Bolt 5.0+ servers
+            "ElementId?" | "StartNodeElementId?" | "EndNodeElementId?" => {
+                let key = match name {
+                    "ElementId?" => ElementDataKey::ElementId,
+                    "StartNodeElementId?" => ElementDataKey::StartNodeElementId,
+                    _ => ElementDataKey::EndNodeElementId,
+                };
+                match self.data.value(key) {
+                    Some(ElementDataValue::Str(BoltString { value: id })) => {
+                        visitation.visit(BorrowedStr(id), visitor)
+                    }
+                    _ => visitor.visit_none(),
+                }
+            }
             "Labels" => {
                 let labels = match self.data.value(ElementDataKey::Labels) {
                     Some(ElementDataValue::Lst(BoltList { value: labels })) => labels,
@@ -183,12 +207,35 @@ impl<'de, T: ElementData<'de>> ElementDataDeserializer<'de, T> {
             _ => Err(DeError::invalid_type(
                 Unexpected::Other(&format!("struct `{name}`")),
                 &concat!(
-                    "one of `Id`, `Labels`, `Type`, `StartNodeId`, ",
-                    "`EndNodeId`, `Keys`, `Nodes`, `Relationships`, or `Indices`"
+                    "one of `Id`, `Labels`, `Type`, `StartNodeId`, `EndNodeId`, ",
+                    "`ElementId`, `StartNodeElementId`, `EndNodeElementId`, ",
+                    "`Keys`, `Nodes`, `Relationships`, or `Indices`"
                 ),
             )),
         }
     }
+    fn element_id<V, A>(
+        self,
+        key: ElementDataKey,
+        visitor: V,
+        visitation: A,
+    ) -> Result<V::Value, DeError>
+    where
+        V: Visitor<'de>,
+        A: Visitation,
+    {
+        let id = match self.data.value(key) {
+            Some(ElementDataValue::Str(BoltString { value: id })) => id,
+            _ => {
+                return Err(DeError::missing_field(match key {
+                    ElementDataKey::StartNodeElementId => "start_node_element_id",
+                    ElementDataKey::EndNodeElementId => "end_node_element_id",
+                    _ => "element_id",
+                }))
+            }
+        };
+        visitation.visit(BorrowedStr(id), visitor)
+    }
 }
 
 impl<'de, T: ElementData<'de>> Deserializer<'de> for ElementDataDeserializer<'de, T> {
@@ -561,17 +608,31 @@ impl<'de, T: ElementData<'de>> IntoDeserializer<'de, DeError> for AdditionalData
     }
 }
 
+/// The items of an element, followed by the element ids that are only present since Bolt 5.0.
+pub type ElementItems<'de, const N: usize, const M: usize> = iter::Chain<
+    array::IntoIter<(ElementDataKey, ElementDataValue<'de>), N>,
+    iter::Flatten<array::IntoIter<Option<(ElementDataKey, ElementDataValue<'de>)>, M>>,
+>;
+
+fn element_id(
+    key: ElementDataKey,
+    id: &Option<BoltString>,
+) -> Option<(ElementDataKey, ElementDataValue<'_>)> {
+    id.as_ref().map(|id| (key, ElementDataValue::Str(id)))
+}
+
 impl<'de> ElementData<'de> for &'de BoltNode {
     fn value(self, key: ElementDataKey) -> Option<ElementDataValue<'de>> {
         match key {
             ElementDataKey::Id => Some(ElementDataValue::Int(&self.id)),
+            ElementDataKey::ElementId => self.element_id.as_ref().map(ElementDataValue::Str),
             ElementDataKey::Labels => Some(ElementDataValue::Lst(&self.labels)),
             ElementDataKey::Properties => Some(ElementDataValue::Map(&self.properties)),
             _ => None,
         }
     }
 
-    type Items = [(ElementDataKey, ElementDataValue<'de>); 3];
+    type Items = ElementItems<'de, 3, 1>;
 
     fn items(self) -> Self::Items {
         [
@@ -582,6 +643,12 @@ impl<'de> ElementData<'de> for &'de BoltNode {
                 ElementDataValue::Map(&self.properties),
             ),
         ]
+        .into_iter()
+        .chain(
+            [element_id(ElementDataKey::ElementId, &self.element_id)]
+                .into_iter()
+                .flatten(),
+        )
     }
 }
 
@@ -591,13 +658,21 @@ impl<'de> ElementData<'de> for &'de BoltRelation {
             ElementDataKey::Id => Some(ElementDataValue::Int(&self.id)),
             ElementDataKey::StartNodeId => Some(ElementDataValue::Int(&self.start_node_id)),
             ElementDataKey::EndNodeId => Some(ElementDataValue::Int(&self.end_node_id)),
+            ElementDataKey::ElementId => self.element_id.as_ref().map(ElementDataValue::Str),
+            ElementDataKey::StartNodeElementId => self
+                .start_node_element_id
+                .as_ref()
+                .map(ElementDataValue::Str),
+            ElementDataKey::EndNodeElementId => {
+                self.end_node_element_id.as_ref().map(ElementDataValue::Str)
+            }
             ElementDataKey::Type => Some(ElementDataValue::Str(&self.typ)),
             ElementDataKey::Properties => Some(ElementDataValue::Map(&self.properties)),
             _ => None,
         }
     }
 
-    type Items = [(ElementDataKey, ElementDataValue<'de>); 5];
+    type Items = ElementItems<'de, 5, 3>;
 
     fn items(self) -> Self::Items {
         [
@@ -616,6 +691,19 @@ impl<'de> ElementData<'de> for &'de BoltRelation {
                 ElementDataValue::Map(&self.properties),
             ),
         ]
+        .into_iter()
+        .chain(
+            [
+                element_id(ElementDataKey::ElementId, &self.element_id),
+                element_id(
+                    ElementDataKey::StartNodeElementId,
+                    &self.start_node_element_id,
+                ),
+                element_id(ElementDataKey::EndNodeElementId, &self.end_node_element_id),
+            ]
+            .into_iter()
+            .flatten(),
+        )
     }
 }
 
@@ -623,13 +711,14 @@ impl<'de> ElementData<'de> for &'de BoltUnboundedRelation {
     fn value(self, key: ElementDataKey) -> Option<ElementDataValue<'de>> {
         match key {
             ElementDataKey::Id => Some(ElementDataValue::Int(&self.id)),
+            ElementDataKey::ElementId => self.element_id.as_ref().map(ElementDataValue::Str),
             ElementDataKey::Type => Some(ElementDataValue::Str(&self.typ)),
             ElementDataKey::Properties => Some(ElementDataValue::Map(&self.properties)),
             _ => None,
         }
     }
 
-    type Items = [(ElementDataKey, ElementDataValue<'de>); 3];
+    type Items = ElementItems<'de, 3, 1>;
 
     fn items(self) -> Self::Items {
         [
@@ -640,6 +729,12 @@ impl<'de> ElementData<'de> for &'de BoltUnboundedRelation {
                 ElementDataValue::Map(&self.properties),
             ),
         ]
+        .into_iter()
+        .chain(
+            [element_id(ElementDataKey::ElementId, &self.element_id)]
+                .into_iter()
+                .flatten(),
+        )
     }
 }
 
@@ -711,7 +806,7 @@ mod tests {
         assert_eq!(node.value(ElementDataKey::Relationships), None);
         assert_eq!(node.value(ElementDataKey::Indices), None);
 
-        let mut items = node.items().into_iter();
+        let mut items = node.items();
         assert_eq!(
             items.next(),
             Some((
@@ -736,6 +831,51 @@ mod tests {
         assert_eq!(items.next(), None);
     }
 
+    #[test]
+    fn element_ids() {
+        let mut node = BoltNode::new(BoltInteger::new(42), BoltList::new(), BoltMap::new());
+        node.element_id = Some(BoltString::from("4:abc:42"));
+
+        assert_eq!(
+            node.value(ElementDataKey::ElementId),
+            Some(ElementDataValue::Str(&BoltString::from("4:abc:42")))
+        );
+        assert_eq!(node.value(ElementDataKey::StartNodeElementId), None);
+        assert_eq!(
+            node.items().last(),
+            Some((
+                ElementDataKey::ElementId,
+                ElementDataValue::Str(&BoltString::from("4:abc:42"))
+            ))
+        );
+
+        let rel = BoltRelation {
+            id: BoltInteger::new(42),
+            start_node_id: BoltInteger::new(1),
+            end_node_id: BoltInteger::new(2),
+            typ: BoltString::from("KNOWS"),
+            properties: BoltMap::new(),
+            element_id: None,
+            start_node_element_id: Some(BoltString::from("4:abc:1")),
+            end_node_element_id: Some(BoltString::from("4:abc:2")),
+        };
+
+        assert_eq!(rel.value(ElementDataKey::ElementId), None);
+        assert_eq!(
+            rel.items().skip(5).collect::<Vec<_>>(),
+            vec![
+                (
+                    ElementDataKey::StartNodeElementId,
+                    ElementDataValue::Str(&BoltString::from("4:abc:1"))
+                ),
+                (
+                    ElementDataKey::EndNodeElementId,
+                    ElementDataValue::Str(&BoltString::from("4:abc:2"))
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn rel_impl() {
         let rel = BoltRelation {
@@ -744,6 +884,9 @@ mod tests {
             end_node_id: BoltInteger::new(2),
             typ: BoltString::from("KNOWS"),
             properties: [("since".into(), 2017.into())].into_iter().collect(),
+            element_id: None,
+            start_node_element_id: None,
+            end_node_element_id: None,
         };
 
         assert_eq!(
@@ -773,7 +916,7 @@ mod tests {
         assert_eq!(rel.value(ElementDataKey::Relationships), None);
         assert_eq!(rel.value(ElementDataKey::Indices), None);
 
-        let mut items = rel.items().into_iter();
+        let mut items = rel.items();
         assert_eq!(
             items.next(),
             Some((
@@ -838,7 +981,7 @@ mod tests {
         assert_eq!(unbounded_rel.value(ElementDataKey::EndNodeId), None);
         assert_eq!(unbounded_rel.value(ElementDataKey::Labels), None);
 
-        let mut items = unbounded_rel.items().into_iter();
+        let mut items = unbounded_rel.items();
         assert_eq!(
             items.next(),
             Some((
@@ -973,6 +1116,9 @@ mod tests {
             end_node_id: BoltInteger::new(2),
             typ: BoltString::from("KNOWS"),
             properties: [("since".into(), 2017.into())].into_iter().collect(),
+            element_id: None,
+            start_node_element_id: None,
+            end_node_element_id: None,
         };
 
         let id = Id::deserialize(ElementDataDeserializer::new(&rel)).unwrap();
@@ -1074,6 +1220,9 @@ mod tests {
             end_node_id: BoltInteger::new(2),
             typ: BoltString::from("KNOWS"),
             properties: [("since".into(), 2017.into())].into_iter().collect(),
+            element_id: None,
+            start_node_element_id: None,
+            end_node_element_id: None,
         };
 
         let knows = HashMap::<ElementDataKey, BoltType>::deserialize(MapAccessDeserializer::new(
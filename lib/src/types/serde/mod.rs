@@ -18,6 +18,8 @@ mod node;
 mod path;
 mod point;
 mod rel;
+#[cfg(feature = "unstable-serde-packstream-format")]
+mod ser;
 mod time;
 mod typ;
 mod urel;
@@ -34,6 +36,25 @@ pub struct StartNodeId(pub u64);
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct EndNodeId(pub u64);
 
+/// Newtype to extract the element id of a node or relationship during deserialization.
+///
+/// Element ids are only sent by servers that speak Bolt 5.0 or newer,
+/// deserializing this from an element of an older server fails.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ElementId<T = String>(pub T);
+
+/// Newtype to extract the start node element id of a relationship during deserialization.
+///
+/// Only available with Bolt 5.0 or newer, see [`ElementId`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StartNodeElementId<T = String>(pub T);
+
+/// Newtype to extract the end node element id of a relationship during deserialization.
+///
+/// Only available with Bolt 5.0 or newer, see [`ElementId`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct EndNodeElementId<T = String>(pub T);
+
 /// Newtype to extract the node labels during deserialization.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Labels<Coll = Vec<String>>(pub Coll);
@@ -1,11 +1,11 @@
 use crate::{
     types::{
         serde::{
-            builder::{BoltNodeBuilder, Id},
+            builder::{BoltNodeBuilder, ElementId, Id},
             element::{ElementDataDeserializer, ElementDataKey},
             BoltKind, Properties,
         },
-        BoltList, BoltNode,
+        BoltList, BoltNode, BoltString,
     },
     BoltMap, DeError, Labels, Node,
 };
@@ -46,8 +46,9 @@ impl<'de> Deserialize<'de> for BoltNode {
         const ID: &str = "42.<id>";
         const LABELS: &str = "42.<labels>";
         const PROPERTIES: &str = "42.<properties>";
+        const ELEMENT_ID: &str = "42.<element_id>";
 
-        const FIELDS: &[&str] = &[ID, LABELS, PROPERTIES];
+        const FIELDS: &[&str] = &[ID, LABELS, PROPERTIES, ELEMENT_ID];
 
         struct BoltNodeVisitor;
 
@@ -72,6 +73,9 @@ impl<'de> Deserialize<'de> for BoltNode {
                         }
                         PROPERTIES => builder
                             .properties(|| map.next_value::<Properties<BoltMap>>().map(|l| l.0))?,
+                        ELEMENT_ID => {
+                            builder.element_id(|| map.next_value::<ElementId>().map(|i| i.0))?
+                        }
                         otherwise => Err(Error::unknown_field(otherwise, FIELDS))?,
                     }
                 }
@@ -105,10 +109,13 @@ impl<'de> Visitor<'de> for BoltNodeVisitor {
                 ElementDataKey::Id => builder.id(|| map.next_value())?,
                 ElementDataKey::Labels => builder.labels(|| map.next_value())?,
                 ElementDataKey::Properties => builder.properties(|| map.next_value())?,
+                ElementDataKey::ElementId => {
+                    builder.element_id(|| map.next_value::<BoltString>().map(Some))?
+                }
                 otherwise => {
                     return Err(Error::unknown_field(
                         otherwise.name(),
-                        &["Id", "Labels", "Properties"],
+                        &["Id", "Labels", "Properties", "ElementId"],
                     ))
                 }
             }
@@ -226,7 +233,7 @@ mod tests {
 
     use crate::{
         types::{BoltInteger, BoltType},
-        Id, Keys,
+        ElementId, Id, Keys,
     };
 
     use super::*;
@@ -247,6 +254,7 @@ mod tests {
             id,
             labels,
             properties,
+            element_id: None,
         }
     }
 
@@ -343,6 +351,48 @@ mod tests {
         test_extract_node_extra(Id { id: 1337.into() });
     }
 
+    #[test]
+    fn extract_node_element_id() {
+        let node = test_node().tap_mut(|n| n.element_id = Some("4:abc:1337".into()));
+        let actual = node.to::<ElementId>().unwrap();
+        assert_eq!(actual, ElementId("4:abc:1337".to_owned()));
+
+        let actual = node.to::<ElementId<&str>>().unwrap();
+        assert_eq!(actual, ElementId("4:abc:1337"));
+    }
+
+    #[test]
+    fn extract_missing_node_element_id() {
+        let node = test_node();
+        let actual = node.to::<ElementId>().unwrap_err();
+        assert!(
+            matches!(
+                actual,
+                DeError::MissingField {
+                    field: "element_id"
+                }
+            ),
+            "{actual:?}"
+        );
+    }
+
+    #[test]
+    fn node_with_element_id_roundtrip() {
+        let node = test_node().tap_mut(|n| n.element_id = Some("4:abc:1337".into()));
+
+        let actual = node.to::<BoltNode>().unwrap();
+        assert_eq!(actual, node);
+
+        let actual = node.to::<BoltType>().unwrap();
+        assert_eq!(actual, BoltType::Node(node.clone()));
+
+        let actual = node.to::<Node>().unwrap();
+        assert_eq!(actual.element_id(), Some("4:abc:1337"));
+
+        let actual = test_node().to::<Node>().unwrap();
+        assert_eq!(actual.element_id(), None);
+    }
+
     #[test]
     fn extract_node_labels() {
         test_extract_node_extra(Labels(vec!["Person".to_owned()]));
@@ -1,7 +1,10 @@
 use crate::{
     types::{
         serde::{
-            builder::{BoltRelationBuilder, EndNodeId, Id, StartNodeId},
+            builder::{
+                BoltRelationBuilder, ElementId, EndNodeElementId, EndNodeId, Id,
+                StartNodeElementId, StartNodeId,
+            },
             element::{ElementDataDeserializer, ElementDataKey},
             BoltKind, Properties,
         },
@@ -48,8 +51,20 @@ impl<'de> Deserialize<'de> for BoltRelation {
         const EID: &str = "42.<end_node_id>";
         const TYP: &str = "42.<type>";
         const PROPS: &str = "42.<properties>";
-
-        const FIELDS: &[&str] = &[ID, SID, EID, TYP, PROPS];
+        const ELEMENT_ID: &str = "42.<element_id>";
+        const SELEMENT_ID: &str = "42.<start_node_element_id>";
+        const EELEMENT_ID: &str = "42.<end_node_element_id>";
+
+        const FIELDS: &[&str] = &[
+            ID,
+            SID,
+            EID,
+            TYP,
+            PROPS,
+            ELEMENT_ID,
+            SELEMENT_ID,
+            EELEMENT_ID,
+        ];
 
         struct BoltRelationVisitor;
 
@@ -77,6 +92,15 @@ impl<'de> Deserialize<'de> for BoltRelation {
                         TYP => builder.typ(|| map.next_value::<Type<BoltString>>().map(|t| t.0))?,
                         PROPS => builder
                             .properties(|| map.next_value::<Properties<BoltMap>>().map(|t| t.0))?,
+                        ELEMENT_ID => {
+                            builder.element_id(|| map.next_value::<ElementId>().map(|i| i.0))?
+                        }
+                        SELEMENT_ID => builder.start_node_element_id(|| {
+                            map.next_value::<StartNodeElementId>().map(|i| i.0)
+                        })?,
+                        EELEMENT_ID => builder.end_node_element_id(|| {
+                            map.next_value::<EndNodeElementId>().map(|i| i.0)
+                        })?,
                         otherwise => Err(Error::unknown_field(otherwise, FIELDS))?,
                     }
                 }
@@ -112,10 +136,28 @@ impl<'de> Visitor<'de> for BoltRelationVisitor {
                 ElementDataKey::EndNodeId => builder.end_node_id(|| map.next_value())?,
                 ElementDataKey::Type => builder.typ(|| map.next_value())?,
                 ElementDataKey::Properties => builder.properties(|| map.next_value())?,
+                ElementDataKey::ElementId => {
+                    builder.element_id(|| map.next_value::<BoltString>().map(Some))?
+                }
+                ElementDataKey::StartNodeElementId => {
+                    builder.start_node_element_id(|| map.next_value::<BoltString>().map(Some))?
+                }
+                ElementDataKey::EndNodeElementId => {
+                    builder.end_node_element_id(|| map.next_value::<BoltString>().map(Some))?
+                }
                 otherwise => {
                     return Err(Error::unknown_field(
                         otherwise.name(),
-                        &["Id", "StartNodeId", "EndNodeId", "Type", "Properties"],
+                        &[
+                            "Id",
+                            "StartNodeId",
+                            "EndNodeId",
+                            "Type",
+                            "Properties",
+                            "ElementId",
+                            "StartNodeElementId",
+                            "EndNodeElementId",
+                        ],
                     ))
                 }
             }
@@ -233,7 +275,7 @@ mod tests {
 
     use crate::{
         types::{BoltInteger, BoltType},
-        EndNodeId, Id, Keys, StartNodeId,
+        ElementId, EndNodeElementId, EndNodeId, Id, Keys, StartNodeElementId, StartNodeId,
     };
 
     use super::*;
@@ -258,6 +300,9 @@ mod tests {
             end_node_id,
             properties,
             typ,
+            element_id: None,
+            start_node_element_id: None,
+            end_node_element_id: None,
         }
     }
 
@@ -579,6 +624,59 @@ mod tests {
         assert_eq!(keys, Keys(["name".to_owned(), "age".to_owned()].into()));
     }
 
+    #[test]
+    fn extract_relation_element_ids() {
+        let rel = test_relation().tap_mut(|r| {
+            r.element_id = Some("5:abc:1337".into());
+            r.start_node_element_id = Some("4:abc:21".into());
+            r.end_node_element_id = Some("4:abc:84".into());
+        });
+
+        assert_eq!(
+            rel.to::<ElementId>().unwrap(),
+            ElementId("5:abc:1337".to_owned())
+        );
+        assert_eq!(
+            rel.to::<StartNodeElementId<&str>>().unwrap(),
+            StartNodeElementId("4:abc:21")
+        );
+        assert_eq!(
+            rel.to::<EndNodeElementId<&str>>().unwrap(),
+            EndNodeElementId("4:abc:84")
+        );
+
+        let actual = test_relation().to::<StartNodeElementId>().unwrap_err();
+        assert!(
+            matches!(
+                actual,
+                DeError::MissingField {
+                    field: "start_node_element_id"
+                }
+            ),
+            "{actual:?}"
+        );
+    }
+
+    #[test]
+    fn relation_with_element_ids_roundtrip() {
+        let rel = test_relation().tap_mut(|r| {
+            r.element_id = Some("5:abc:1337".into());
+            r.start_node_element_id = Some("4:abc:21".into());
+            r.end_node_element_id = Some("4:abc:84".into());
+        });
+
+        let actual = rel.to::<BoltRelation>().unwrap();
+        assert_eq!(actual, rel);
+
+        let actual = rel.to::<BoltType>().unwrap();
+        assert_eq!(actual, BoltType::Relation(rel.clone()));
+
+        let actual = rel.to::<Relation>().unwrap();
+        assert_eq!(actual.element_id(), Some("5:abc:1337"));
+        assert_eq!(actual.start_node_element_id(), Some("4:abc:21"));
+        assert_eq!(actual.end_node_element_id(), Some("4:abc:84"));
+    }
+
     #[test]
     fn relation_to_bolt_type() {
         let relation = test_relation();
@@ -0,0 +1,105 @@
+use crate::types::{BoltList, BoltMap, BoltString, BoltType};
+
+use serde::{
+    ser::{Error, SerializeMap, SerializeSeq},
+    Serialize, Serializer,
+};
+
+/// Only the types that can be used as parameters or metadata are supported,
+/// graph elements and temporal or spatial values fail to serialize.
+impl Serialize for BoltType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            BoltType::String(s) => s.serialize(serializer),
+            BoltType::Boolean(b) => serializer.serialize_bool(b.value),
+            BoltType::Map(m) => m.serialize(serializer),
+            BoltType::Null(_) => serializer.serialize_none(),
+            BoltType::Integer(i) => serializer.serialize_i64(i.value),
+            BoltType::Float(f) => serializer.serialize_f64(f.value),
+            BoltType::List(l) => l.serialize(serializer),
+            BoltType::Bytes(b) => serializer.serialize_bytes(&b.value),
+            otherwise => Err(S::Error::custom(format_args!(
+                "serializing {otherwise:?} is not supported"
+            ))),
+        }
+    }
+}
+
+impl Serialize for BoltString {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.value)
+    }
+}
+
+impl Serialize for BoltList {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.value.len()))?;
+        for value in &self.value {
+            seq.serialize_element(value)?;
+        }
+        seq.end()
+    }
+}
+
+impl Serialize for BoltMap {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.value.len()))?;
+        for (key, value) in &self.value {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        packstream::{bolt, to_bytes},
+        types::{BoltDate, BoltNull},
+    };
+
+    #[test]
+    fn serialize_values() {
+        let value = BoltType::List(BoltList::from(vec![
+            BoltType::from(42_i64),
+            BoltType::from("foo"),
+            BoltType::from(true),
+            BoltType::Null(BoltNull),
+            BoltType::Map([("bar".into(), 1.5_f64.into())].into_iter().collect()),
+        ]));
+
+        let bytes = to_bytes(&value).unwrap();
+
+        let expected = bolt()
+            .tiny_list(5)
+            .tiny_int(42)
+            .tiny_string("foo")
+            .bool(true)
+            .null()
+            .tiny_map(1)
+            .tiny_string("bar")
+            .float(1.5)
+            .build();
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn serialize_unsupported() {
+        let value = BoltType::Date(BoltDate::from(chrono::NaiveDate::MIN));
+        assert!(to_bytes(&value).is_err());
+    }
+}
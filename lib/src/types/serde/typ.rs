@@ -1182,6 +1182,7 @@ mod tests {
             id,
             labels,
             properties,
+            element_id: None,
         };
         let node = BoltType::Node(node);
 
@@ -1226,6 +1227,9 @@ mod tests {
             end_node_id,
             properties,
             typ,
+            element_id: None,
+            start_node_element_id: None,
+            end_node_element_id: None,
         };
         let relation = BoltType::Relation(relation);
 
@@ -1266,6 +1270,7 @@ mod tests {
             id,
             properties,
             typ,
+            element_id: None,
         };
         let relation = BoltType::UnboundedRelation(relation);
 
@@ -1,7 +1,7 @@
 use crate::{
     types::{
         serde::{
-            builder::{BoltUnboundedRelationBuilder, Id},
+            builder::{BoltUnboundedRelationBuilder, ElementId, Id},
             element::{ElementDataDeserializer, ElementDataKey},
             BoltKind, Properties,
         },
@@ -46,8 +46,9 @@ impl<'de> Deserialize<'de> for BoltUnboundedRelation {
         const ID: &str = "42.<id>";
         const TYP: &str = "42.<type>";
         const PROPS: &str = "42.<properties>";
+        const ELEMENT_ID: &str = "42.<element_id>";
 
-        const FIELDS: &[&str] = &[ID, TYP, PROPS];
+        const FIELDS: &[&str] = &[ID, TYP, PROPS, ELEMENT_ID];
 
         struct BoltUnboundedRelationVisitor;
 
@@ -70,6 +71,9 @@ impl<'de> Deserialize<'de> for BoltUnboundedRelation {
                         TYP => builder.typ(|| map.next_value::<Type<BoltString>>().map(|t| t.0))?,
                         PROPS => builder
                             .properties(|| map.next_value::<Properties<BoltMap>>().map(|o| o.0))?,
+                        ELEMENT_ID => {
+                            builder.element_id(|| map.next_value::<ElementId>().map(|i| i.0))?
+                        }
                         otherwise => Err(Error::unknown_field(otherwise, FIELDS))?,
                     }
                 }
@@ -107,10 +111,13 @@ impl<'de> Visitor<'de> for BoltUnboundedRelationVisitor {
                 ElementDataKey::Id => builder.id(|| map.next_value())?,
                 ElementDataKey::Type => builder.typ(|| map.next_value())?,
                 ElementDataKey::Properties => builder.properties(|| map.next_value())?,
+                ElementDataKey::ElementId => {
+                    builder.element_id(|| map.next_value::<BoltString>().map(Some))?
+                }
                 otherwise => {
                     return Err(Error::unknown_field(
                         otherwise.name(),
-                        &["Id", "Type", "Properties"],
+                        &["Id", "Type", "Properties", "ElementId"],
                     ))
                 }
             }
@@ -249,6 +256,7 @@ mod tests {
             id,
             properties,
             typ,
+            element_id: None,
         }
     }
 
@@ -511,6 +519,22 @@ mod tests {
         assert_eq!(keys, Keys(["name".to_owned(), "age".to_owned()].into()));
     }
 
+    #[test]
+    fn unbounded_relation_with_element_id_roundtrip() {
+        let rel = test_unbounded_relation().tap_mut(|r| r.element_id = Some("5:abc:1337".into()));
+
+        assert_eq!(
+            rel.to::<crate::ElementId>().unwrap(),
+            crate::ElementId("5:abc:1337".to_owned())
+        );
+
+        let actual = rel.to::<BoltUnboundedRelation>().unwrap();
+        assert_eq!(actual, rel);
+
+        let actual = rel.to::<UnboundedRelation>().unwrap();
+        assert_eq!(actual.element_id(), Some("5:abc:1337"));
+    }
+
     #[test]
     fn unbounded_relation_to_bolt_type() {
         let unbounded_relation = test_unbounded_relation();
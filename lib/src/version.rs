@@ -10,19 +10,81 @@ pub enum Version {
     V4_1,
     V4_3,
     V4_4,
+    V5_0,
+    V5_1,
+    V5_2,
+    V5_3,
+    V5_4,
 }
 
 impl Version {
+    /// Sent as the first handshake slot to offer the manifest style negotiation.
+    /// A server that supports it answers with the same value.
+    pub(crate) const MANIFEST_V1: [u8; 4] = [0x00, 0x00, 0x01, 0xFF];
+
+    /// All supported versions, from newest to oldest.
+    const SUPPORTED: [Version; 8] = [
+        Version::V5_4,
+        Version::V5_3,
+        Version::V5_2,
+        Version::V5_1,
+        Version::V5_0,
+        Version::V4_4,
+        Version::V4_3,
+        Version::V4,
+    ];
+
     pub fn add_supported_versions(bytes: &mut BytesMut) {
         bytes.reserve(16);
-        bytes.put_u32(0x0404); // V4_4
-        bytes.put_u32(0x0304); // V4_3
-        bytes.put_u32(0x0104); // V4_1
-        bytes.put_u32(0x0004); // V4
+        bytes.put_slice(&Self::MANIFEST_V1);
+        bytes.put_u32(0x00_04_04_05); // V5_4 down to V5_0
+        bytes.put_u32(0x00_01_04_04); // V4_4 down to V4_3
+
+        // servers before 4.3 do not understand ranges, and those that speak 4.1 also speak 4.0
+        bytes.put_u32(0x00_00_00_04); // V4
+    }
+
+    /// Picks the newest supported version from the versions offered in a handshake manifest.
+    ///
+    /// Every offered entry has the same layout as a handshake slot: `[0, range, minor, major]`,
+    /// covering the versions `major.minor` down to `major.(minor - range)`.
+    pub(crate) fn select(offered: &[[u8; 4]]) -> Option<Version> {
+        Self::SUPPORTED.into_iter().find(|version| {
+            let (major, minor) = version.major_minor();
+            offered.iter().any(|&[_, range, max_minor, offered_major]| {
+                offered_major == major
+                    && minor <= max_minor
+                    && minor >= max_minor.saturating_sub(range)
+            })
+        })
+    }
+
+    pub(crate) fn to_bytes(self) -> [u8; 4] {
+        let (major, minor) = self.major_minor();
+        [0, 0, minor, major]
+    }
+
+    fn major_minor(self) -> (u8, u8) {
+        match self {
+            Version::V4 => (4, 0),
+            Version::V4_1 => (4, 1),
+            Version::V4_3 => (4, 3),
+            Version::V4_4 => (4, 4),
+            Version::V5_0 => (5, 0),
+            Version::V5_1 => (5, 1),
+            Version::V5_2 => (5, 2),
+            Version::V5_3 => (5, 3),
+            Version::V5_4 => (5, 4),
+        }
     }
 
     pub fn parse(version_bytes: [u8; 4]) -> Result<Version> {
         match version_bytes {
+            [0, 0, 4, 5] => Ok(Version::V5_4),
+            [0, 0, 3, 5] => Ok(Version::V5_3),
+            [0, 0, 2, 5] => Ok(Version::V5_2),
+            [0, 0, 1, 5] => Ok(Version::V5_1),
+            [0, 0, 0, 5] => Ok(Version::V5_0),
             [0, 0, 4, 4] => Ok(Version::V4_4),
             [0, 0, 3, 4] => Ok(Version::V4_3),
             [0, 0, 1, 4] => Ok(Version::V4_1),
@@ -35,12 +97,8 @@ impl Version {
 
 impl Display for Version {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Version::V4 => write!(f, "4.0"),
-            Version::V4_1 => write!(f, "4.1"),
-            Version::V4_3 => write!(f, "4.3"),
-            Version::V4_4 => write!(f, "4.4"),
-        }
+        let (major, minor) = self.major_minor();
+        write!(f, "{major}.{minor}")
     }
 }
 
@@ -50,9 +108,69 @@ mod tests {
 
     #[tokio::test]
     async fn should_parse_version() {
+        assert_eq!(Version::parse([0, 0, 4, 5]).unwrap(), Version::V5_4);
+        assert_eq!(Version::parse([0, 0, 3, 5]).unwrap(), Version::V5_3);
+        assert_eq!(Version::parse([0, 0, 2, 5]).unwrap(), Version::V5_2);
+        assert_eq!(Version::parse([0, 0, 1, 5]).unwrap(), Version::V5_1);
+        assert_eq!(Version::parse([0, 0, 0, 5]).unwrap(), Version::V5_0);
         assert_eq!(Version::parse([0, 0, 4, 4]).unwrap(), Version::V4_4);
         assert_eq!(Version::parse([0, 0, 3, 4]).unwrap(), Version::V4_3);
         assert_eq!(Version::parse([0, 0, 1, 4]).unwrap(), Version::V4_1);
         assert_eq!(Version::parse([0, 0, 0, 4]).unwrap(), Version::V4);
     }
+
+    #[test]
+    fn should_reject_unsupported_version() {
+        assert!(matches!(
+            Version::parse([0, 0, 5, 5]),
+            Err(Error::UnsupportedVersion(5, 5))
+        ));
+        assert!(matches!(
+            Version::parse([0x48, 0x54, 0x54, 0x50]),
+            Err(Error::ProtocolMismatch(0x4854_5450))
+        ));
+    }
+
+    #[test]
+    fn should_propose_version_ranges() {
+        let mut bytes = BytesMut::new();
+        Version::add_supported_versions(&mut bytes);
+        assert_eq!(
+            &bytes[..],
+            &[
+                0x00, 0x00, 0x01, 0xFF, // manifest v1
+                0x00, 0x04, 0x04, 0x05, // 5.4 - 5.0
+                0x00, 0x01, 0x04, 0x04, // 4.4 - 4.3
+                0x00, 0x00, 0x00, 0x04, // 4.0
+            ]
+        );
+    }
+
+    #[test]
+    fn should_select_newest_version_from_manifest() {
+        assert_eq!(Version::select(&[[0, 8, 8, 5]]), Some(Version::V5_4));
+        assert_eq!(
+            Version::select(&[[0, 0, 2, 5], [0, 2, 4, 4]]),
+            Some(Version::V5_2)
+        );
+        assert_eq!(Version::select(&[[0, 0, 4, 4]]), Some(Version::V4_4));
+        assert_eq!(Version::select(&[[0, 1, 1, 4]]), Some(Version::V4));
+        assert_eq!(Version::select(&[[0, 3, 8, 5]]), None);
+        assert_eq!(Version::select(&[[0, 0, 0, 6]]), None);
+        assert_eq!(Version::select(&[]), None);
+    }
+
+    #[test]
+    fn should_write_version_bytes() {
+        for version in Version::SUPPORTED {
+            assert_eq!(Version::parse(version.to_bytes()).unwrap(), version);
+        }
+    }
+
+    #[test]
+    fn should_order_versions() {
+        assert!(Version::V5_0 > Version::V4_4);
+        assert!(Version::V5_4 > Version::V5_1);
+        assert_eq!(Version::V5_2.to_string(), "5.2");
+    }
 }